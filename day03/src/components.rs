@@ -0,0 +1,115 @@
+use crate::schematic::{EngineSchematic, Pos2, SchematicNumber};
+use std::collections::{BTreeMap, HashMap};
+
+pub struct Component {
+    pub symbols: Vec<(Pos2, char)>,
+    pub part_numbers: Vec<SchematicNumber>,
+}
+
+impl Component {
+    pub fn size(&self) -> usize {
+        self.symbols.len() + self.part_numbers.len()
+    }
+
+    pub fn sum(&self) -> u128 {
+        self.part_numbers
+            .iter()
+            .map(|number| u128::from(number.value))
+            .sum()
+    }
+
+    pub fn product(&self) -> Option<u128> {
+        self.part_numbers.iter().try_fold(1u128, |acc, number| {
+            acc.checked_mul(u128::from(number.value))
+        })
+    }
+}
+
+pub fn connected_components(schematic: &EngineSchematic) -> Vec<Component> {
+    let numbers = schematic.numbers();
+    let number_indices: HashMap<Pos2, usize> = numbers
+        .iter()
+        .enumerate()
+        .map(|(index, number)| (number.position, index))
+        .collect();
+
+    let symbols = schematic.special_characters();
+
+    // Numbers occupy the node indices 0..numbers.len(), symbols the ones after that.
+    let mut union_find = UnionFind::new(numbers.len() + symbols.len());
+    for (symbol_index, (pos, _)) in symbols.iter().enumerate() {
+        for number in schematic.adjacent_numbers(pos.x, pos.y) {
+            union_find.union(
+                numbers.len() + symbol_index,
+                number_indices[&number.position],
+            );
+        }
+    }
+
+    let mut components: BTreeMap<usize, Component> = BTreeMap::new();
+    for (symbol_index, &symbol) in symbols.iter().enumerate() {
+        let root = union_find.find(numbers.len() + symbol_index);
+        components
+            .entry(root)
+            .or_insert_with(|| Component {
+                symbols: Vec::new(),
+                part_numbers: Vec::new(),
+            })
+            .symbols
+            .push(symbol);
+    }
+    for (number_index, &number) in numbers.iter().enumerate() {
+        let root = union_find.find(number_index);
+        if let Some(component) = components.get_mut(&root) {
+            component.part_numbers.push(number);
+        }
+    }
+
+    let mut components: Vec<Component> = components.into_values().collect();
+    components.sort_by_key(|component| {
+        let (first_symbol_pos, _) = component.symbols[0];
+        (first_symbol_pos.y, first_symbol_pos.x)
+    });
+    components
+}
+
+pub fn largest_components(components: &[Component], n: usize) -> Vec<&Component> {
+    let mut largest: Vec<&Component> = components.iter().collect();
+    largest.sort_by(|a, b| b.size().cmp(&a.size()).then(b.sum().cmp(&a.sum())));
+    largest.truncate(n);
+    largest
+}
+
+struct UnionFind {
+    parents: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(size: usize) -> UnionFind {
+        UnionFind {
+            parents: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, node: usize) -> usize {
+        let mut root = node;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut node = node;
+        while self.parents[node] != root {
+            let parent = self.parents[node];
+            self.parents[node] = root;
+            node = parent;
+        }
+
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let root_a = self.find(a);
+        let root_b = self.find(b);
+        self.parents[root_a] = root_b;
+    }
+}
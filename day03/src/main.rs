@@ -1,74 +1,154 @@
+mod components;
+mod render;
 mod schematic;
+mod streaming;
+
+use components::Component;
+use eyre::anyhow;
+use itertools::Itertools;
+use render::{RenderFormat, RenderOptions};
+use schematic::{EngineSchematic, ParseMode, StorageKind};
+use std::{
+    env,
+    fs::{self, File},
+    io::BufReader,
+};
+
+fn solve_task_1(schematic: &EngineSchematic) -> eyre::Result<u128> {
+    schematic
+        .numbers()
+        .iter()
+        .filter(|number| schematic.is_part_number(number))
+        .try_fold(0u128, |sum, number| {
+            sum.checked_add(u128::from(number.value))
+        })
+        .ok_or(anyhow!("sum of part numbers overflows u128"))
+}
 
-use schematic::{EngineSchematic, SchematicSymbol};
-use std::{collections::HashSet, fs};
-
-fn solve_task_1(schematic: &EngineSchematic) -> u32 {
-    let mut part_numbers = Vec::<u32>::new();
-
-    for y in 0..schematic.dimensions().y {
-        let mut skip_next_digits_until_non_digit = false;
-
-        for x in 0..schematic.dimensions().x {
-            let is_digit = schematic.get(x, y).unwrap().is_digit();
-
-            match (is_digit, skip_next_digits_until_non_digit) {
-                (true, true) => continue,
-                (false, true) => skip_next_digits_until_non_digit = false,
-                _ => (),
-            }
+fn solve_task_2(schematic: &EngineSchematic) -> eyre::Result<u128> {
+    schematic
+        .gears()
+        .iter()
+        .try_fold(0u128, |sum, gear| sum.checked_add(gear.ratio()))
+        .ok_or(anyhow!("sum of gear ratios overflows u128"))
+}
 
-            if is_digit {
-                let is_adjacent_to_special_char = schematic
-                    .adjacent_positions(x, y)
-                    .iter()
-                    .map(|pos| schematic.get(pos.x, pos.y).unwrap())
-                    .any(SchematicSymbol::is_special_character);
-
-                if is_adjacent_to_special_char {
-                    let part_number = schematic.get_complete_number(x, y).unwrap();
-                    part_numbers.push(part_number);
-                    skip_next_digits_until_non_digit = true;
-                }
+fn render(schematic: &EngineSchematic, args: &[String]) -> eyre::Result<()> {
+    let mut options = RenderOptions {
+        format: RenderFormat::Ansi,
+        legend: false,
+        gear_ratios: false,
+    };
+    let mut output_path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "ansi" => options.format = RenderFormat::Ansi,
+            "html" => options.format = RenderFormat::Html,
+            "--legend" => options.legend = true,
+            "--gear-ratios" => options.gear_ratios = true,
+            "--output" => {
+                output_path = Some(args.next().ok_or(anyhow!("--output expects a path"))?);
             }
+            _ => return Err(anyhow!("unknown render argument: {arg}")),
         }
     }
 
-    part_numbers.iter().sum()
+    let rendered = render::render(schematic, &options);
+    match output_path {
+        Some(path) => fs::write(path, rendered)?,
+        None => print!("{rendered}"),
+    }
+
+    Ok(())
 }
 
-fn solve_task_2(schematic: &EngineSchematic) -> u32 {
-    let mut gear_ratios: Vec<u32> = Vec::new();
-
-    for y in 0..schematic.dimensions().y {
-        for x in 0..schematic.dimensions().x {
-            if let Some(SchematicSymbol::SpecialCharacter('*')) = schematic.get(x, y) {
-                // The use of a set is not really correct since the gear symbol could be adjacent to
-                // multiple identical part numbers.
-                // However that doesn't happen for my input schematic so I don't care right now.
-                let adjacent_part_numbers: HashSet<u32> = schematic
-                    .adjacent_positions(x, y)
-                    .iter()
-                    .filter_map(|pos| schematic.get_complete_number(pos.x, pos.y))
-                    .collect();
-
-                if adjacent_part_numbers.len() == 2 {
-                    gear_ratios.push(adjacent_part_numbers.iter().product());
-                }
-            }
-        }
+fn print_components(schematic: &EngineSchematic, args: &[String]) -> eyre::Result<()> {
+    let n_largest = match args {
+        [] => 3,
+        [flag, n] if flag == "--top" => n.parse()?,
+        _ => return Err(anyhow!("usage: components [--top N]")),
+    };
+
+    let format_component = |component: &Component| {
+        let symbols = component
+            .symbols
+            .iter()
+            .map(|(pos, char)| format!("{char} ({}, {})", pos.x, pos.y))
+            .join(", ");
+        let part_numbers = component
+            .part_numbers
+            .iter()
+            .map(|number| number.value)
+            .join(", ");
+        let product = match component.product() {
+            Some(product) => product.to_string(),
+            None => "overflow".to_string(),
+        };
+        format!(
+            "symbols [{symbols}], part numbers [{part_numbers}], sum {}, product {product}",
+            component.sum()
+        )
+    };
+
+    let components = components::connected_components(schematic);
+    for (index, component) in components.iter().enumerate() {
+        println!("Component {index}: {}", format_component(component));
     }
 
-    gear_ratios.iter().sum()
+    println!();
+    println!("Largest components:");
+    for component in components::largest_components(&components, n_largest) {
+        println!("size {}: {}", component.size(), format_component(component));
+    }
+
+    Ok(())
 }
 
 fn main() -> eyre::Result<()> {
-    let input = fs::read_to_string("input")?;
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    if args.first().is_some_and(|command| command == "stream") {
+        let (task_1, task_2) = streaming::solve_tasks(BufReader::new(File::open("input")?))?;
+        println!("Task 1: {task_1}");
+        println!("Task 2: {task_2}");
+        return Ok(());
+    }
 
-    let schematic: EngineSchematic = input.as_str().try_into()?;
+    let mut take_flag = |flag: &str| match args.iter().position(|arg| arg == flag) {
+        Some(index) => {
+            args.remove(index);
+            true
+        }
+        None => false,
+    };
+    let storage_kind = if take_flag("--sparse") {
+        StorageKind::Sparse
+    } else {
+        StorageKind::Dense
+    };
+    let parse_mode = if take_flag("--lenient") {
+        ParseMode::Lenient
+    } else {
+        ParseMode::Strict
+    };
+
+    let input = fs::read_to_string("input")?;
+    let (schematic, warnings) = EngineSchematic::parse(&input, storage_kind, parse_mode)?;
+    for warning in warnings {
+        eprintln!("Warning: {warning}");
+    }
 
-    println!("Task 1: {}", solve_task_1(&schematic));
-    println!("Task 2: {}", solve_task_2(&schematic));
+    match args.first().map(String::as_str) {
+        Some("render") => render(&schematic, &args[1..])?,
+        Some("components") => print_components(&schematic, &args[1..])?,
+        Some(command) => return Err(anyhow!("unknown command: {command}")),
+        None => {
+            println!("Task 1: {}", solve_task_1(&schematic)?);
+            println!("Task 2: {}", solve_task_2(&schematic)?);
+        }
+    }
 
     Ok(())
 }
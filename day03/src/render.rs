@@ -0,0 +1,275 @@
+use crate::schematic::{EngineSchematic, Gear, SchematicSymbol};
+use std::fmt::Write;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RenderFormat {
+    Ansi,
+    Html,
+}
+
+pub struct RenderOptions {
+    pub format: RenderFormat,
+    pub legend: bool,
+    pub gear_ratios: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CellClass {
+    Empty,
+    PartNumber,
+    NonPartNumber,
+    Gear,
+    Symbol,
+}
+
+impl CellClass {
+    const ALL: [CellClass; 5] = [
+        CellClass::PartNumber,
+        CellClass::NonPartNumber,
+        CellClass::Gear,
+        CellClass::Symbol,
+        CellClass::Empty,
+    ];
+
+    fn description(self) -> &'static str {
+        match self {
+            CellClass::Empty => "empty",
+            CellClass::PartNumber => "part number",
+            CellClass::NonPartNumber => "non-part number",
+            CellClass::Gear => "gear",
+            CellClass::Symbol => "other symbol",
+        }
+    }
+
+    fn sample(self) -> &'static str {
+        match self {
+            CellClass::Empty => ".",
+            CellClass::PartNumber | CellClass::NonPartNumber => "123",
+            CellClass::Gear => "*",
+            CellClass::Symbol => "#",
+        }
+    }
+
+    fn ansi_code(self) -> &'static str {
+        match self {
+            CellClass::Empty => "2",
+            CellClass::PartNumber => "1;32",
+            CellClass::NonPartNumber => "31",
+            CellClass::Gear => "1;33",
+            CellClass::Symbol => "36",
+        }
+    }
+
+    fn css_class(self) -> &'static str {
+        match self {
+            CellClass::Empty => "empty",
+            CellClass::PartNumber => "part",
+            CellClass::NonPartNumber => "non-part",
+            CellClass::Gear => "gear",
+            CellClass::Symbol => "symbol",
+        }
+    }
+}
+
+pub fn render(schematic: &EngineSchematic, options: &RenderOptions) -> String {
+    let gears = schematic.gears();
+    let classes = classify(schematic, &gears);
+
+    match options.format {
+        RenderFormat::Ansi => render_ansi(schematic, &classes, &gears, options),
+        RenderFormat::Html => render_html(schematic, &classes, &gears, options),
+    }
+}
+
+fn classify(schematic: &EngineSchematic, gears: &[Gear]) -> Vec<Vec<CellClass>> {
+    let dimensions = schematic.dimensions();
+    let mut classes = vec![vec![CellClass::Empty; dimensions.x]; dimensions.y];
+
+    for number in schematic.numbers() {
+        let class = if schematic.is_part_number(&number) {
+            CellClass::PartNumber
+        } else {
+            CellClass::NonPartNumber
+        };
+        for pos in number.positions() {
+            classes[pos.y][pos.x] = class;
+        }
+    }
+
+    for (y, row) in classes.iter_mut().enumerate() {
+        for (x, class) in row.iter_mut().enumerate() {
+            if schematic.get(x, y).unwrap().is_special_character() {
+                *class = CellClass::Symbol;
+            }
+        }
+    }
+
+    for gear in gears {
+        classes[gear.position.y][gear.position.x] = CellClass::Gear;
+    }
+
+    classes
+}
+
+fn symbol_char(symbol: SchematicSymbol) -> char {
+    match symbol {
+        SchematicSymbol::Digit(digit) => char::from_digit(digit, 10).unwrap(),
+        SchematicSymbol::Nothing => '.',
+        SchematicSymbol::SpecialCharacter(char) => char,
+    }
+}
+
+fn gear_annotation(gear: &Gear) -> String {
+    let [first, second] = gear.part_numbers;
+    format!(
+        "gear ({}, {}): {first} * {second} = {}",
+        gear.position.x,
+        gear.position.y,
+        gear.ratio()
+    )
+}
+
+fn render_ansi(
+    schematic: &EngineSchematic,
+    classes: &[Vec<CellClass>],
+    gears: &[Gear],
+    options: &RenderOptions,
+) -> String {
+    let paint = |class: CellClass, text: &str| format!("\x1b[{}m{text}\x1b[0m", class.ansi_code());
+    let mut output = String::new();
+
+    if options.legend {
+        for class in CellClass::ALL {
+            writeln!(
+                output,
+                "{} {}",
+                paint(class, class.sample()),
+                class.description()
+            )
+            .unwrap();
+        }
+        output.push('\n');
+    }
+
+    for (y, row) in classes.iter().enumerate() {
+        for (x, &class) in row.iter().enumerate() {
+            let char = symbol_char(schematic.get(x, y).unwrap());
+            output.push_str(&paint(class, &char.to_string()));
+        }
+
+        if options.gear_ratios {
+            let annotations: Vec<String> = gears
+                .iter()
+                .filter(|gear| gear.position.y == y)
+                .map(gear_annotation)
+                .collect();
+            if !annotations.is_empty() {
+                let annotations = annotations.join(", ");
+                write!(output, "  {}", paint(CellClass::Gear, &annotations)).unwrap();
+            }
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
+
+fn escape_html(char: char) -> String {
+    match char {
+        '&' => "&amp;".to_string(),
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        _ => char.to_string(),
+    }
+}
+
+fn render_html(
+    schematic: &EngineSchematic,
+    classes: &[Vec<CellClass>],
+    gears: &[Gear],
+    options: &RenderOptions,
+) -> String {
+    let mut output = String::new();
+
+    output.push_str(concat!(
+        "<!DOCTYPE html>\n",
+        "<html>\n",
+        "<head>\n",
+        "<meta charset=\"utf-8\">\n",
+        "<title>Engine schematic</title>\n",
+        "<style>\n",
+        "body { background: #0f0f23; color: #cccccc; font-family: monospace; }\n",
+        ".empty { color: #444444; }\n",
+        ".part { color: #00cc00; font-weight: bold; }\n",
+        ".non-part { color: #ff5555; }\n",
+        ".gear { color: #ffff66; font-weight: bold; }\n",
+        ".symbol { color: #33cccc; }\n",
+        ".annotation { color: #ffff66; }\n",
+        "</style>\n",
+        "</head>\n",
+        "<body>\n",
+    ));
+
+    if options.legend {
+        output.push_str("<ul>\n");
+        for class in CellClass::ALL {
+            writeln!(
+                output,
+                "<li><span class=\"{}\">{}</span> {}</li>",
+                class.css_class(),
+                class.sample(),
+                class.description()
+            )
+            .unwrap();
+        }
+        output.push_str("</ul>\n");
+    }
+
+    output.push_str("<pre>\n");
+    for (y, row) in classes.iter().enumerate() {
+        for (x, &class) in row.iter().enumerate() {
+            let char = escape_html(symbol_char(schematic.get(x, y).unwrap()));
+            if class == CellClass::Gear {
+                let gear = gears
+                    .iter()
+                    .find(|gear| gear.position.x == x && gear.position.y == y)
+                    .unwrap();
+                write!(
+                    output,
+                    "<span class=\"gear\" title=\"{}\">{char}</span>",
+                    gear_annotation(gear)
+                )
+                .unwrap();
+            } else {
+                write!(
+                    output,
+                    "<span class=\"{}\">{char}</span>",
+                    class.css_class()
+                )
+                .unwrap();
+            }
+        }
+
+        if options.gear_ratios {
+            let annotations: Vec<String> = gears
+                .iter()
+                .filter(|gear| gear.position.y == y)
+                .map(gear_annotation)
+                .collect();
+            if !annotations.is_empty() {
+                write!(
+                    output,
+                    "  <span class=\"annotation\">{}</span>",
+                    annotations.join(", ")
+                )
+                .unwrap();
+            }
+        }
+
+        output.push('\n');
+    }
+    output.push_str("</pre>\n</body>\n</html>\n");
+
+    output
+}
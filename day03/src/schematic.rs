@@ -1,19 +1,60 @@
 use eyre::anyhow;
-use std::iter::repeat;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter},
+};
 
 pub struct EngineSchematic {
     dimensions: Dim2,
-    contents: Vec2<SchematicSymbol>,
+    contents: Storage,
 }
 
-impl EngineSchematic {
-    fn new(dimensions: Dim2) -> EngineSchematic {
-        let mut contents = Vec2::new();
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum StorageKind {
+    Dense,
+    Sparse,
+}
 
-        for _ in 0..dimensions.y {
-            let row = Vec::from_iter(repeat(SchematicSymbol::Nothing).take(dimensions.x));
-            contents.push(row);
-        }
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    Strict,
+    Lenient,
+}
+
+pub struct ParseWarning {
+    pub line: usize,
+    pub width: usize,
+    pub expected_width: usize,
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {} has {} columns, padded to {}",
+            self.line, self.width, self.expected_width
+        )
+    }
+}
+
+enum Storage {
+    Dense(Vec2<SchematicSymbol>),
+    Sparse(HashMap<Pos2, SchematicSymbol>),
+}
+
+impl EngineSchematic {
+    fn new(dimensions: Dim2, storage_kind: StorageKind) -> EngineSchematic {
+        let contents = match storage_kind {
+            StorageKind::Dense => {
+                let mut contents = Vec2::new();
+                for _ in 0..dimensions.y {
+                    contents.push(vec![SchematicSymbol::Nothing; dimensions.x]);
+                }
+                Storage::Dense(contents)
+            }
+            StorageKind::Sparse => Storage::Sparse(HashMap::new()),
+        };
 
         EngineSchematic {
             dimensions,
@@ -21,19 +62,159 @@ impl EngineSchematic {
         }
     }
 
+    pub fn parse(
+        schematic_string: &str,
+        storage_kind: StorageKind,
+        parse_mode: ParseMode,
+    ) -> eyre::Result<(Self, Vec<ParseWarning>)> {
+        let mut lines: Vec<&str> = schematic_string
+            .lines()
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect();
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+
+        let widths: Vec<usize> = lines.iter().map(|line| line.chars().count()).collect();
+        let size_x = match parse_mode {
+            ParseMode::Strict => *widths.first().ok_or(anyhow!("input string is empty"))?,
+            ParseMode::Lenient => *widths
+                .iter()
+                .max()
+                .ok_or(anyhow!("input string is empty"))?,
+        };
+        let size_y = lines.len();
+
+        let mut warnings = Vec::new();
+        let mut width_errors = Vec::new();
+        for (y, &width) in widths.iter().enumerate() {
+            let line = y + 1;
+            match (parse_mode, width.cmp(&size_x)) {
+                (_, Ordering::Equal) => (),
+                (ParseMode::Strict, Ordering::Less) => width_errors.push(format!(
+                    "line {line} ends after column {width}, expected {size_x} columns"
+                )),
+                (ParseMode::Strict, Ordering::Greater) => width_errors.push(format!(
+                    "line {line} has an unexpected character in column {}, expected {size_x} columns",
+                    size_x + 1
+                )),
+                (ParseMode::Lenient, _) => warnings.push(ParseWarning {
+                    line,
+                    width,
+                    expected_width: size_x,
+                }),
+            }
+        }
+        if !width_errors.is_empty() {
+            return Err(anyhow!(width_errors.join("\n")));
+        }
+
+        let mut schematic = EngineSchematic::new(
+            Dim2 {
+                x: size_x,
+                y: size_y,
+            },
+            storage_kind,
+        );
+
+        for (y, line) in lines.iter().enumerate() {
+            // The number currently being read, as its value and start column.
+            let mut number: Option<(u64, usize)> = None;
+
+            for (x, char) in line.chars().enumerate() {
+                let symbol = SchematicSymbol::try_from(char).map_err(|_| {
+                    anyhow!(
+                        "line {} contains an invalid character in column {}: {char}",
+                        y + 1,
+                        x + 1
+                    )
+                })?;
+
+                number = match (symbol, number) {
+                    (SchematicSymbol::Digit(digit), Some((value, start))) => {
+                        let value = value
+                            .checked_mul(10)
+                            .and_then(|value| value.checked_add(u64::from(digit)))
+                            .ok_or(anyhow!(
+                                "line {} contains a number starting in column {} that overflows u64",
+                                y + 1,
+                                start + 1
+                            ))?;
+                        Some((value, start))
+                    }
+                    (SchematicSymbol::Digit(digit), None) => Some((u64::from(digit), x)),
+                    _ => None,
+                };
+
+                if symbol != SchematicSymbol::Nothing {
+                    schematic.set(x, y, symbol).unwrap();
+                }
+            }
+        }
+
+        Ok((schematic, warnings))
+    }
+
     pub fn get(&self, x: usize, y: usize) -> Option<SchematicSymbol> {
-        self.contents.get(y)?.get(x).cloned()
+        if x >= self.dimensions.x || y >= self.dimensions.y {
+            return None;
+        }
+
+        match &self.contents {
+            Storage::Dense(contents) => contents.get(y)?.get(x).cloned(),
+            Storage::Sparse(contents) => Some(
+                contents
+                    .get(&Pos2 { x, y })
+                    .cloned()
+                    .unwrap_or(SchematicSymbol::Nothing),
+            ),
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, symbol: SchematicSymbol) -> Option<()> {
+        if x >= self.dimensions.x || y >= self.dimensions.y {
+            return None;
+        }
+
+        match &mut self.contents {
+            Storage::Dense(contents) => *contents.get_mut(y)?.get_mut(x)? = symbol,
+            Storage::Sparse(contents) => {
+                if symbol == SchematicSymbol::Nothing {
+                    contents.remove(&Pos2 { x, y });
+                } else {
+                    contents.insert(Pos2 { x, y }, symbol);
+                }
+            }
+        }
+
+        Some(())
     }
 
-    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut SchematicSymbol> {
-        self.contents.get_mut(y)?.get_mut(x)
+    pub fn occupied_positions(&self) -> Vec<Pos2> {
+        match &self.contents {
+            Storage::Dense(contents) => contents
+                .iter()
+                .enumerate()
+                .flat_map(|(y, row)| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|(_, &symbol)| symbol != SchematicSymbol::Nothing)
+                        .map(move |(x, _)| Pos2 { x, y })
+                })
+                .collect(),
+            Storage::Sparse(contents) => {
+                let mut positions: Vec<Pos2> = contents.keys().copied().collect();
+                positions.sort_by_key(|pos| (pos.y, pos.x));
+                positions
+            }
+        }
     }
 
     pub fn dimensions(&self) -> Dim2 {
         self.dimensions.clone()
     }
 
-    pub fn get_complete_number(&self, x: usize, y: usize) -> Option<u32> {
+    pub fn number_at(&self, x: usize, y: usize) -> Option<SchematicNumber> {
         if let Some(SchematicSymbol::Digit(_)) = self.get(x, y) {
             let mut first_digit_x = x;
             for x_to_check in (0..x).rev() {
@@ -43,21 +224,88 @@ impl EngineSchematic {
                 first_digit_x -= 1;
             }
 
-            let mut complete_number = 0;
+            // Parsing rejects numbers that overflow u64.
+            let mut value = 0;
+            let mut length = 0;
             for x_to_get in first_digit_x..self.dimensions.x {
                 if let SchematicSymbol::Digit(digit) = self.get(x_to_get, y).unwrap() {
-                    complete_number = complete_number * 10 + digit;
+                    value = value * 10 + u64::from(digit);
+                    length += 1;
                 } else {
                     break;
                 }
             }
 
-            Some(complete_number)
+            Some(SchematicNumber {
+                value,
+                position: Pos2 {
+                    x: first_digit_x,
+                    y,
+                },
+                length,
+            })
         } else {
             None
         }
     }
 
+    pub fn numbers(&self) -> Vec<SchematicNumber> {
+        let mut numbers: Vec<SchematicNumber> = Vec::new();
+
+        for pos in self.occupied_positions() {
+            let already_covered = numbers.last().is_some_and(|number| {
+                number.position.y == pos.y && pos.x < number.position.x + number.length
+            });
+
+            if !already_covered {
+                numbers.extend(self.number_at(pos.x, pos.y));
+            }
+        }
+
+        numbers
+    }
+
+    pub fn special_characters(&self) -> Vec<(Pos2, char)> {
+        self.occupied_positions()
+            .into_iter()
+            .filter_map(|pos| match self.get(pos.x, pos.y) {
+                Some(SchematicSymbol::SpecialCharacter(char)) => Some((pos, char)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn adjacent_numbers(&self, x: usize, y: usize) -> Vec<SchematicNumber> {
+        let mut seen_start_positions = HashSet::new();
+
+        self.adjacent_positions(x, y)
+            .iter()
+            .filter_map(|pos| self.number_at(pos.x, pos.y))
+            .filter(|number| seen_start_positions.insert(number.position))
+            .collect()
+    }
+
+    pub fn is_part_number(&self, number: &SchematicNumber) -> bool {
+        number
+            .positions()
+            .flat_map(|pos| self.adjacent_positions(pos.x, pos.y))
+            .any(|pos| self.get(pos.x, pos.y).unwrap().is_special_character())
+    }
+
+    pub fn gears(&self) -> Vec<Gear> {
+        self.special_characters()
+            .into_iter()
+            .filter(|&(_, char)| char == '*')
+            .filter_map(|(pos, _)| match self.adjacent_numbers(pos.x, pos.y)[..] {
+                [first, second] => Some(Gear {
+                    position: pos,
+                    part_numbers: [first.value, second.value],
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn adjacent_positions(&self, x: usize, y: usize) -> Vec<Pos2> {
         let mut adjacent_positions = Vec::new();
 
@@ -99,41 +347,28 @@ impl TryFrom<&str> for EngineSchematic {
     type Error = eyre::Error;
 
     fn try_from(schematic_string: &str) -> Result<Self, Self::Error> {
-        let size_x = schematic_string
-            .lines()
-            .next()
-            .ok_or(anyhow!("input string is empty"))?
-            .len();
-        let size_y = schematic_string.lines().count();
+        EngineSchematic::parse(schematic_string, StorageKind::Dense, ParseMode::Strict)
+            .map(|(schematic, _)| schematic)
+    }
+}
 
-        let mut schematic = EngineSchematic::new(Dim2 {
-            x: size_x,
-            y: size_y,
-        });
+impl TryFrom<char> for SchematicSymbol {
+    type Error = eyre::Error;
 
-        for (y, line) in schematic_string.lines().enumerate() {
-            for (x, char) in line.chars().enumerate() {
-                let to_modify = schematic
-                    .get_mut(x, y)
-                    .ok_or(anyhow!("line {y} is too long, expected {size_x}"))?;
-
-                if let Some(digit) = char.to_digit(10) {
-                    *to_modify = SchematicSymbol::Digit(digit);
-                } else if "+-*/=#@%$&".contains(char) {
-                    *to_modify = SchematicSymbol::SpecialCharacter(char);
-                } else if char != '.' {
-                    return Err(anyhow!(
-                        "line {y} contains and invalid character in column {x}: {char}"
-                    ));
-                }
-            }
+    fn try_from(char: char) -> Result<Self, Self::Error> {
+        if let Some(digit) = char.to_digit(10) {
+            Ok(SchematicSymbol::Digit(digit))
+        } else if "+-*/=#@%$&".contains(char) {
+            Ok(SchematicSymbol::SpecialCharacter(char))
+        } else if char == '.' {
+            Ok(SchematicSymbol::Nothing)
+        } else {
+            Err(anyhow!("invalid character: {char}"))
         }
-
-        Ok(schematic)
     }
 }
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq)]
 pub enum SchematicSymbol {
     Digit(u32),
     Nothing,
@@ -142,17 +377,37 @@ pub enum SchematicSymbol {
 
 impl SchematicSymbol {
     pub fn is_special_character(self) -> bool {
-        match self {
-            SchematicSymbol::SpecialCharacter(_) => true,
-            _ => false,
-        }
+        matches!(self, SchematicSymbol::SpecialCharacter(_))
     }
 
     pub fn is_digit(self) -> bool {
-        match self {
-            SchematicSymbol::Digit(_) => true,
-            _ => false,
-        }
+        matches!(self, SchematicSymbol::Digit(_))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct SchematicNumber {
+    pub value: u64,
+    pub position: Pos2,
+    pub length: usize,
+}
+
+impl SchematicNumber {
+    pub fn positions(&self) -> impl Iterator<Item = Pos2> {
+        let Pos2 { x, y } = self.position;
+        (x..x + self.length).map(move |x| Pos2 { x, y })
+    }
+}
+
+pub struct Gear {
+    pub position: Pos2,
+    pub part_numbers: [u64; 2],
+}
+
+impl Gear {
+    pub fn ratio(&self) -> u128 {
+        let [first, second] = self.part_numbers;
+        u128::from(first) * u128::from(second)
     }
 }
 
@@ -162,7 +417,7 @@ pub struct Dim2 {
     pub y: usize,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub struct Pos2 {
     pub x: usize,
     pub y: usize,
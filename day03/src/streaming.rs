@@ -0,0 +1,129 @@
+use crate::schematic::SchematicSymbol;
+use eyre::anyhow;
+use std::{collections::VecDeque, io::BufRead};
+
+type Row = Vec<SchematicSymbol>;
+
+struct RowNumber {
+    value: u64,
+    start: usize,
+    length: usize,
+}
+
+impl RowNumber {
+    fn touches_column(&self, x: usize) -> bool {
+        self.start <= x + 1 && x <= self.start + self.length
+    }
+}
+
+pub fn solve_tasks<R: BufRead>(reader: R) -> eyre::Result<(u128, u128)> {
+    let mut window: VecDeque<Row> = VecDeque::from([Row::new()]);
+    let mut solutions = (0, 0);
+
+    for (y, line) in reader.lines().enumerate() {
+        let row = line?
+            .chars()
+            .enumerate()
+            .map(|(x, char)| {
+                SchematicSymbol::try_from(char).map_err(|_| {
+                    anyhow!(
+                        "line {} contains an invalid character in column {}: {char}",
+                        y + 1,
+                        x + 1
+                    )
+                })
+            })
+            .collect::<eyre::Result<Row>>()?;
+        numbers_in(&row).map_err(|error| anyhow!("line {} {error}", y + 1))?;
+
+        window.push_back(row);
+        if window.len() == 3 {
+            add_solutions_for_middle_row(&window, &mut solutions)?;
+            window.pop_front();
+        }
+    }
+
+    if window.len() == 2 {
+        window.push_back(Row::new());
+        add_solutions_for_middle_row(&window, &mut solutions)?;
+    }
+
+    Ok(solutions)
+}
+
+fn add_solutions_for_middle_row(
+    window: &VecDeque<Row>,
+    solutions: &mut (u128, u128),
+) -> eyre::Result<()> {
+    let middle_row = &window[1];
+    let numbers_per_row: Vec<Vec<RowNumber>> = window
+        .iter()
+        .map(|row| numbers_in(row))
+        .collect::<eyre::Result<_>>()?;
+
+    for number in &numbers_per_row[1] {
+        let is_part_number = window.iter().any(|row| {
+            (number.start.saturating_sub(1)..=number.start + number.length)
+                .filter_map(|x| row.get(x))
+                .any(|symbol| symbol.is_special_character())
+        });
+
+        if is_part_number {
+            solutions.0 = solutions
+                .0
+                .checked_add(u128::from(number.value))
+                .ok_or(anyhow!("sum of part numbers overflows u128"))?;
+        }
+    }
+
+    for (x, symbol) in middle_row.iter().enumerate() {
+        if *symbol != SchematicSymbol::SpecialCharacter('*') {
+            continue;
+        }
+
+        let adjacent_numbers: Vec<u64> = numbers_per_row
+            .iter()
+            .flatten()
+            .filter(|number| number.touches_column(x))
+            .map(|number| number.value)
+            .collect();
+
+        if let [first, second] = adjacent_numbers[..] {
+            solutions.1 = solutions
+                .1
+                .checked_add(u128::from(first) * u128::from(second))
+                .ok_or(anyhow!("sum of gear ratios overflows u128"))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn numbers_in(row: &[SchematicSymbol]) -> eyre::Result<Vec<RowNumber>> {
+    let mut numbers: Vec<RowNumber> = Vec::new();
+
+    for (x, symbol) in row.iter().enumerate() {
+        if let SchematicSymbol::Digit(digit) = symbol {
+            match numbers.last_mut() {
+                Some(number) if number.start + number.length == x => {
+                    number.value = number
+                        .value
+                        .checked_mul(10)
+                        .and_then(|value| value.checked_add(u64::from(*digit)))
+                        .ok_or(anyhow!(
+                            "contains a number starting in column {} that overflows u64",
+                            number.start + 1
+                        ))?;
+                    number.length += 1;
+                }
+                _ => numbers.push(RowNumber {
+                    value: u64::from(*digit),
+                    start: x,
+                    length: 1,
+                }),
+            }
+        }
+    }
+
+    Ok(numbers)
+}
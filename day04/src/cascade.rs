@@ -0,0 +1,65 @@
+use crate::rules::CopyRule;
+use eyre::{anyhow, Error};
+
+pub fn count_scratchcards(
+    n_matches_per_card: &[usize],
+    copy_rule: &dyn CopyRule,
+) -> Result<u128, Error> {
+    cascade(n_matches_per_card, copy_rule, |_, _, _| ())?
+        .iter()
+        .try_fold(0u128, |total, &n_copies| total.checked_add(n_copies))
+        .ok_or(anyhow!("number of scratchcards overflows u128"))
+}
+
+// Copies won from later cards (e.g. when wrapping around) are scratched in a further pass. Without
+// such backward wins everything is settled in the first pass.
+pub fn cascade(
+    n_matches_per_card: &[usize],
+    copy_rule: &dyn CopyRule,
+    mut on_copies_won: impl FnMut(usize, usize, u128),
+) -> Result<Vec<u128>, Error> {
+    let n_cards = n_matches_per_card.len();
+    let overflow = || anyhow!("number of scratchcards overflows u128");
+
+    let mut n_copies_per_card = vec![0u128; n_cards];
+    let mut n_unscratched_copies_per_card = vec![1u128; n_cards];
+
+    for _ in 0..=n_cards {
+        if n_unscratched_copies_per_card
+            .iter()
+            .all(|&n_copies| n_copies == 0)
+        {
+            return Ok(n_copies_per_card);
+        }
+
+        for (card_index, &n_matches) in n_matches_per_card.iter().enumerate() {
+            let n_copies = std::mem::take(&mut n_unscratched_copies_per_card[card_index]);
+            if n_copies == 0 {
+                continue;
+            }
+            n_copies_per_card[card_index] = n_copies_per_card[card_index]
+                .checked_add(n_copies)
+                .ok_or_else(overflow)?;
+
+            for won_card_index in copy_rule.won_card_indices(card_index, n_matches, n_cards) {
+                let n_owned_copies = n_copies_per_card[won_card_index]
+                    .checked_add(n_unscratched_copies_per_card[won_card_index])
+                    .ok_or_else(overflow)?;
+                let n_won_copies = match copy_rule.max_copies_per_card() {
+                    Some(max_copies) => n_copies.min(max_copies.saturating_sub(n_owned_copies)),
+                    None => n_copies,
+                };
+
+                if n_won_copies > 0 {
+                    n_unscratched_copies_per_card[won_card_index] = n_unscratched_copies_per_card
+                        [won_card_index]
+                        .checked_add(n_won_copies)
+                        .ok_or_else(overflow)?;
+                    on_copies_won(card_index, won_card_index, n_won_copies);
+                }
+            }
+        }
+    }
+
+    Err(anyhow!("the cascade of won scratchcards never ends"))
+}
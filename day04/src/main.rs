@@ -1,55 +1,116 @@
-use eyre::{Error, Result};
-use std::{collections::HashSet, fs};
+use eyre::{anyhow, Error, Result};
+use rules::WinNextCards;
+use scratchcard::{parse_scratchcards, Scratchcard};
+use std::{env, fs, time::Instant};
+
+mod cascade;
+mod number_set;
+mod rules;
+mod scratchcard;
+mod trace;
 
 fn main() -> Result<(), Error> {
-    let input = fs::read_to_string("input")?;
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let mut take_option = |option: &str| -> Result<Option<String>, Error> {
+        match args.iter().position(|arg| arg == option) {
+            Some(index) if index + 1 < args.len() => {
+                args.remove(index);
+                Ok(Some(args.remove(index)))
+            }
+            Some(_) => Err(anyhow!("{option} expects a value")),
+            None => Ok(None),
+        }
+    };
+    let point_rule = rules::point_rule_from_name(
+        &take_option("--points")?.unwrap_or_else(|| "doubling".to_string()),
+    )?;
+    let copy_rule = rules::copy_rule_from_name(
+        &take_option("--copies")?.unwrap_or_else(|| "next".to_string()),
+    )?;
 
-    let numbers: Vec<(HashSet<u32>, HashSet<u32>)> = input
-        .lines()
-        .map(|line| {
-            let (_, numbers) = line.split_once(':').unwrap();
-            let (winning, have) = numbers.split_once('|').unwrap();
-            let parse_numbers = |string: &str| -> HashSet<u32> {
-                string
-                    .split_whitespace()
-                    .map(str::parse::<u32>)
-                    .map(Result::unwrap)
-                    .collect()
-            };
-            (parse_numbers(winning), parse_numbers(have))
-        })
-        .collect();
+    if let Some("bench") = args.first().map(String::as_str) {
+        return bench(&args[1..]);
+    }
 
-    let n_matches_per_card: Vec<usize> = numbers
-        .iter()
-        .map(|(winning, have)| winning.intersection(have).count())
-        .collect();
+    let input = fs::read_to_string("input")?;
 
-    // task 1
-    let points = n_matches_per_card.iter().map(|&n_matches| match n_matches {
-        0 => 0,
-        _ => 1 << (n_matches - 1),
-    });
-    println!("Task 1: {}", points.sum::<usize>());
+    let scratchcards = parse_scratchcards(&input)?;
 
-    // task 2
-    let mut n_scratchcards = 0;
-    let mut card_indices_to_process: Vec<usize> = (0..numbers.len()).collect();
-    let mut new_card_indices = Vec::<usize>::new();
-
-    while !card_indices_to_process.is_empty() {
-        for card_index in card_indices_to_process {
-            n_scratchcards += 1;
-            let n_card_matches = n_matches_per_card[card_index];
-            let indices_of_won_cards = ((card_index + 1)..(card_index + 1 + n_card_matches))
-                .take_while(|&card_index| card_index < numbers.len());
-            new_card_indices.extend(indices_of_won_cards);
+    if let Some("trace") = args.first().map(String::as_str) {
+        let traces = trace::trace(&scratchcards, point_rule.as_ref(), copy_rule.as_ref())?;
+        match args.get(1).map(String::as_str) {
+            None | Some("table") => print!("{}", trace::format_table(&traces)),
+            Some("dot") => print!("{}", trace::format_dot(&traces)),
+            Some(format) => return Err(anyhow!("unknown trace format: {format}")),
         }
-        card_indices_to_process = new_card_indices;
-        new_card_indices = Vec::new();
+        return Ok(());
     }
 
+    let n_matches_per_card: Vec<usize> = scratchcards.iter().map(Scratchcard::n_matches).collect();
+
+    // task 1
+    let total_points = n_matches_per_card
+        .iter()
+        .try_fold(0u128, |total_points, &n_matches| {
+            total_points.checked_add(point_rule.points(n_matches)?)
+        })
+        .ok_or(anyhow!("total points overflow u128"))?;
+    println!("Task 1: {total_points}");
+
+    // task 2
+    let n_scratchcards = cascade::count_scratchcards(&n_matches_per_card, copy_rule.as_ref())?;
     println!("Task 2: {}", n_scratchcards);
 
     Ok(())
 }
+
+fn bench(args: &[String]) -> Result<(), Error> {
+    let sizes: Vec<usize> = match args {
+        [] => vec![1_000, 10_000, 100_000, 500_000],
+        _ => args
+            .iter()
+            .map(|arg| arg.parse())
+            .collect::<Result<_, _>>()?,
+    };
+
+    let mut state: u64 = 0x2023_1204;
+    let mut next_random = || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 33) as u32
+    };
+
+    for n_cards in sizes {
+        let scratchcards: Vec<Scratchcard> = (1..=n_cards as u32)
+            .map(|id| {
+                let winning_numbers = (0..10).map(|_| next_random() % 100).collect();
+                let numbers = (0..25).map(|_| next_random() % 100).collect();
+                Scratchcard::new(id, winning_numbers, numbers)
+            })
+            .collect();
+
+        let start = Instant::now();
+        let n_matches: usize = scratchcards.iter().map(Scratchcard::n_matches).sum();
+        let elapsed = start.elapsed();
+
+        println!("{n_cards:>9} cards: {n_matches:>12} matches in {elapsed:?}");
+
+        // Most cards don't win anything so that the number of copies stays bounded instead of
+        // growing exponentially.
+        let n_matches_per_card: Vec<usize> = (0..n_cards)
+            .map(|_| match next_random() {
+                random if random.is_multiple_of(8) => random as usize % 11,
+                _ => 0,
+            })
+            .collect();
+
+        let start = Instant::now();
+        let n_scratchcards = cascade::count_scratchcards(&n_matches_per_card, &WinNextCards)?;
+        let elapsed = start.elapsed();
+
+        println!("{n_cards:>9} cards: {n_scratchcards:>12} scratchcards in {elapsed:?}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+const BITSET_WORDS: usize = 4;
+const BITSET_LIMIT: u32 = (BITSET_WORDS * 64) as u32;
+
+// Scratchcard numbers are small, so most sets fit into a fixed-size bitset and intersecting two of
+// them is a handful of popcounts. Sets containing larger numbers fall back to a sorted vector.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NumberSet {
+    Bits([u64; BITSET_WORDS]),
+    Sorted(Vec<u32>),
+}
+
+impl NumberSet {
+    pub fn new() -> NumberSet {
+        NumberSet::Bits([0; BITSET_WORDS])
+    }
+
+    pub fn insert(&mut self, number: u32) -> bool {
+        if let NumberSet::Bits(_) = self {
+            if number >= BITSET_LIMIT {
+                *self = NumberSet::Sorted(self.to_sorted_vec());
+            }
+        }
+
+        match self {
+            NumberSet::Bits(words) => {
+                let word = &mut words[number as usize / 64];
+                let mask = 1 << (number % 64);
+                let is_new = *word & mask == 0;
+                *word |= mask;
+                is_new
+            }
+            NumberSet::Sorted(numbers) => match numbers.binary_search(&number) {
+                Ok(_) => false,
+                Err(index) => {
+                    numbers.insert(index, number);
+                    true
+                }
+            },
+        }
+    }
+
+    pub fn contains(&self, number: u32) -> bool {
+        match self {
+            NumberSet::Bits(words) => {
+                number < BITSET_LIMIT && words[number as usize / 64] & (1 << (number % 64)) != 0
+            }
+            NumberSet::Sorted(numbers) => numbers.binary_search(&number).is_ok(),
+        }
+    }
+
+    pub fn intersection_count(&self, other: &NumberSet) -> usize {
+        match (self, other) {
+            (NumberSet::Bits(words), NumberSet::Bits(other_words)) => words
+                .iter()
+                .zip(other_words)
+                .map(|(word, other_word)| (word & other_word).count_ones() as usize)
+                .sum(),
+            (NumberSet::Sorted(numbers), NumberSet::Sorted(other_numbers)) => {
+                let (mut index, mut other_index, mut count) = (0, 0, 0);
+                while index < numbers.len() && other_index < other_numbers.len() {
+                    match numbers[index].cmp(&other_numbers[other_index]) {
+                        std::cmp::Ordering::Less => index += 1,
+                        std::cmp::Ordering::Greater => other_index += 1,
+                        std::cmp::Ordering::Equal => {
+                            count += 1;
+                            index += 1;
+                            other_index += 1;
+                        }
+                    }
+                }
+                count
+            }
+            (NumberSet::Sorted(numbers), bits) | (bits, NumberSet::Sorted(numbers)) => numbers
+                .iter()
+                .filter(|&&number| bits.contains(number))
+                .count(),
+        }
+    }
+
+    fn to_sorted_vec(&self) -> Vec<u32> {
+        match self {
+            NumberSet::Bits(_) => (0..BITSET_LIMIT)
+                .filter(|&number| self.contains(number))
+                .collect(),
+            NumberSet::Sorted(numbers) => numbers.clone(),
+        }
+    }
+}
+
+impl FromIterator<u32> for NumberSet {
+    fn from_iter<T: IntoIterator<Item = u32>>(iter: T) -> Self {
+        let mut set = NumberSet::new();
+        for number in iter {
+            set.insert(number);
+        }
+        set
+    }
+}
@@ -0,0 +1,101 @@
+use eyre::{anyhow, Error};
+
+pub trait PointRule {
+    // Returns None if the points overflow u128.
+    fn points(&self, n_matches: usize) -> Option<u128>;
+}
+
+pub trait CopyRule {
+    fn won_card_indices(&self, card_index: usize, n_matches: usize, n_cards: usize) -> Vec<usize>;
+
+    fn max_copies_per_card(&self) -> Option<u128> {
+        None
+    }
+}
+
+pub struct DoublingPoints;
+
+impl PointRule for DoublingPoints {
+    fn points(&self, n_matches: usize) -> Option<u128> {
+        match n_matches {
+            0 => Some(0),
+            _ => 1u128.checked_shl(u32::try_from(n_matches - 1).ok()?),
+        }
+    }
+}
+
+pub struct LinearPoints;
+
+impl PointRule for LinearPoints {
+    fn points(&self, n_matches: usize) -> Option<u128> {
+        u128::try_from(n_matches).ok()
+    }
+}
+
+pub struct FibonacciPoints;
+
+impl PointRule for FibonacciPoints {
+    fn points(&self, n_matches: usize) -> Option<u128> {
+        let (mut current, mut next) = (0u128, 1u128);
+        for _ in 0..n_matches {
+            (current, next) = (next, current.checked_add(next)?);
+        }
+        Some(current)
+    }
+}
+
+pub struct WinNextCards;
+
+impl CopyRule for WinNextCards {
+    fn won_card_indices(&self, card_index: usize, n_matches: usize, n_cards: usize) -> Vec<usize> {
+        ((card_index + 1)..(card_index + 1 + n_matches).min(n_cards)).collect()
+    }
+}
+
+pub struct WinNextCardsWrapping;
+
+impl CopyRule for WinNextCardsWrapping {
+    fn won_card_indices(&self, card_index: usize, n_matches: usize, n_cards: usize) -> Vec<usize> {
+        ((card_index + 1)..(card_index + 1 + n_matches))
+            .map(|won_card_index| won_card_index % n_cards)
+            .collect()
+    }
+}
+
+pub struct CappedCopies {
+    pub max_copies_per_card: u128,
+}
+
+impl CopyRule for CappedCopies {
+    fn won_card_indices(&self, card_index: usize, n_matches: usize, n_cards: usize) -> Vec<usize> {
+        WinNextCards.won_card_indices(card_index, n_matches, n_cards)
+    }
+
+    fn max_copies_per_card(&self) -> Option<u128> {
+        Some(self.max_copies_per_card)
+    }
+}
+
+pub fn point_rule_from_name(name: &str) -> Result<Box<dyn PointRule>, Error> {
+    match name {
+        "doubling" => Ok(Box::new(DoublingPoints)),
+        "linear" => Ok(Box::new(LinearPoints)),
+        "fibonacci" => Ok(Box::new(FibonacciPoints)),
+        _ => Err(anyhow!(
+            "unknown point rule {name:?}, expected doubling, linear or fibonacci"
+        )),
+    }
+}
+
+pub fn copy_rule_from_name(name: &str) -> Result<Box<dyn CopyRule>, Error> {
+    match name.split_once(':') {
+        None if name == "next" => Ok(Box::new(WinNextCards)),
+        None if name == "wraparound" => Ok(Box::new(WinNextCardsWrapping)),
+        Some(("capped", max_copies_per_card)) => Ok(Box::new(CappedCopies {
+            max_copies_per_card: max_copies_per_card.parse()?,
+        })),
+        _ => Err(anyhow!(
+            "unknown copy rule {name:?}, expected next, wraparound or capped:<max copies>"
+        )),
+    }
+}
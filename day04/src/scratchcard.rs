@@ -0,0 +1,98 @@
+use crate::number_set::NumberSet;
+use eyre::{anyhow, Error};
+use std::{collections::HashSet, str::FromStr};
+
+pub struct Scratchcard {
+    id: u32,
+    winning_numbers: NumberSet,
+    numbers: NumberSet,
+}
+
+impl Scratchcard {
+    pub fn new(id: u32, winning_numbers: NumberSet, numbers: NumberSet) -> Scratchcard {
+        Scratchcard {
+            id,
+            winning_numbers,
+            numbers,
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn n_matches(&self) -> usize {
+        self.winning_numbers.intersection_count(&self.numbers)
+    }
+}
+
+impl FromStr for Scratchcard {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (card, numbers) = line
+            .split_once(':')
+            .ok_or(anyhow!("missing ':' after the card ID"))?;
+
+        let id = card
+            .strip_prefix("Card")
+            .ok_or(anyhow!("missing \"Card\" prefix"))?
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("invalid card ID: {:?}", card.trim()))?;
+
+        let (winning_numbers, numbers) = numbers.split_once('|').ok_or(anyhow!(
+            "missing '|' between winning numbers and held numbers"
+        ))?;
+
+        Ok(Scratchcard {
+            id,
+            winning_numbers: parse_numbers(winning_numbers, "winning numbers")?,
+            numbers: parse_numbers(numbers, "held numbers")?,
+        })
+    }
+}
+
+fn parse_numbers(string: &str, description: &str) -> Result<NumberSet, Error> {
+    let mut numbers = NumberSet::new();
+
+    for word in string.split_whitespace() {
+        let number = word
+            .parse()
+            .map_err(|_| anyhow!("invalid number in {description}: {word:?}"))?;
+        if !numbers.insert(number) {
+            return Err(anyhow!("repeated number in {description}: {number}"));
+        }
+    }
+
+    Ok(numbers)
+}
+
+pub fn parse_scratchcards(input: &str) -> Result<Vec<Scratchcard>, Error> {
+    let mut scratchcards: Vec<Scratchcard> = Vec::new();
+    let mut seen_ids = HashSet::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let scratchcard: Scratchcard = line
+            .parse()
+            .map_err(|error| anyhow!("line {line_number}: {error}"))?;
+
+        let expected_id = scratchcards.len() as u32 + 1;
+        if !seen_ids.insert(scratchcard.id) {
+            return Err(anyhow!(
+                "line {line_number}: duplicated card ID {}",
+                scratchcard.id
+            ));
+        } else if scratchcard.id != expected_id {
+            return Err(anyhow!(
+                "line {line_number}: non-sequential card ID {}, expected {expected_id}",
+                scratchcard.id
+            ));
+        }
+
+        scratchcards.push(scratchcard);
+    }
+
+    Ok(scratchcards)
+}
@@ -0,0 +1,118 @@
+use crate::{
+    cascade::cascade,
+    rules::{CopyRule, PointRule},
+    scratchcard::Scratchcard,
+};
+use eyre::{anyhow, Error};
+use itertools::Itertools;
+use std::fmt::Write;
+
+pub struct CardTrace {
+    pub id: u32,
+    pub n_matches: usize,
+    pub points: u128,
+    pub n_copies: u128,
+    pub copies_received_from: Vec<(u32, u128)>,
+    pub won_card_ids: Vec<u32>,
+}
+
+pub fn trace(
+    scratchcards: &[Scratchcard],
+    point_rule: &dyn PointRule,
+    copy_rule: &dyn CopyRule,
+) -> Result<Vec<CardTrace>, Error> {
+    let n_matches_per_card: Vec<usize> = scratchcards.iter().map(Scratchcard::n_matches).collect();
+    let mut copies_received_from = vec![Vec::<(u32, u128)>::new(); scratchcards.len()];
+
+    let n_copies_per_card = cascade(&n_matches_per_card, copy_rule, |from, to, n_copies| {
+        let from_id = scratchcards[from].id();
+        match copies_received_from[to]
+            .iter_mut()
+            .find(|(id, _)| *id == from_id)
+        {
+            Some((_, n_received_copies)) => *n_received_copies += n_copies,
+            None => copies_received_from[to].push((from_id, n_copies)),
+        }
+    })?;
+
+    scratchcards
+        .iter()
+        .zip(n_matches_per_card)
+        .zip(n_copies_per_card)
+        .zip(copies_received_from)
+        .enumerate()
+        .map(
+            |(card_index, (((scratchcard, n_matches), n_copies), copies_received_from))| {
+                Ok(CardTrace {
+                    id: scratchcard.id(),
+                    n_matches,
+                    points: point_rule
+                        .points(n_matches)
+                        .ok_or(anyhow!("points of card {} overflow u128", scratchcard.id()))?,
+                    n_copies,
+                    copies_received_from,
+                    won_card_ids: copy_rule
+                        .won_card_indices(card_index, n_matches, scratchcards.len())
+                        .into_iter()
+                        .map(|won_card_index| scratchcards[won_card_index].id())
+                        .collect(),
+                })
+            },
+        )
+        .collect()
+}
+
+pub fn format_table(traces: &[CardTrace]) -> String {
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        "{:>6} | {:>7} | {:>6} | {:>10} | {:<40} | Wins cards",
+        "Card", "Matches", "Points", "Copies", "Copies received from"
+    )
+    .unwrap();
+
+    for trace in traces {
+        let received_from = trace
+            .copies_received_from
+            .iter()
+            .map(|(id, n_copies)| format!("{id} (x{n_copies})"))
+            .join(", ");
+        let won_cards = trace.won_card_ids.iter().join(", ");
+
+        let row = format!(
+            "{:>6} | {:>7} | {:>6} | {:>10} | {:<40} | {}",
+            trace.id, trace.n_matches, trace.points, trace.n_copies, received_from, won_cards
+        );
+        writeln!(output, "{}", row.trim_end()).unwrap();
+    }
+
+    output
+}
+
+pub fn format_dot(traces: &[CardTrace]) -> String {
+    let mut output = String::from("digraph scratchcards {\n");
+
+    for trace in traces {
+        writeln!(
+            output,
+            "    card{} [label=\"Card {}\\n{} matches, {} points\\n{} copies\"];",
+            trace.id, trace.id, trace.n_matches, trace.points, trace.n_copies
+        )
+        .unwrap();
+    }
+
+    for trace in traces {
+        for (id, n_copies) in &trace.copies_received_from {
+            writeln!(
+                output,
+                "    card{id} -> card{} [label=\"{n_copies}\"];",
+                trace.id
+            )
+            .unwrap();
+        }
+    }
+
+    output.push_str("}\n");
+    output
+}
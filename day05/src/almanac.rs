@@ -0,0 +1,321 @@
+use crate::{
+    interval::{merge_intervals, Interval},
+    piecewise::{PiecewiseMap, Segment},
+};
+use eyre::{anyhow, Error};
+use std::{collections::HashMap, str::FromStr};
+
+pub struct Almanac {
+    seeds: Vec<u64>,
+    maps: Vec<AlmanacMap>,
+}
+
+pub struct AlmanacMap {
+    pub source: String,
+    pub destination: String,
+    pub ranges: Vec<MapRange>,
+}
+
+#[derive(Clone, Copy)]
+pub struct MapRange {
+    pub destination_start: u64,
+    pub source_start: u64,
+    pub length: u64,
+    pub line: usize,
+}
+
+impl Almanac {
+    pub fn seeds(&self) -> &[u64] {
+        &self.seeds
+    }
+
+    pub fn maps(&self) -> &[AlmanacMap] {
+        &self.maps
+    }
+
+    pub fn path(&self, seed: u64) -> Result<Vec<u64>, Error> {
+        let mut path = vec![seed];
+        for map in &self.maps {
+            path.push(map.map_value(*path.last().unwrap())?);
+        }
+        Ok(path)
+    }
+
+    pub fn categories(&self) -> Vec<&str> {
+        let mut categories = vec!["seed"];
+        categories.extend(self.maps.iter().map(|map| map.destination.as_str()));
+        categories
+    }
+
+    fn category_index(&self, category: &str) -> Result<usize, Error> {
+        self.categories()
+            .iter()
+            .position(|&known_category| known_category == category)
+            .ok_or(anyhow!(
+                "unknown category {category:?}, expected one of {:?}",
+                self.categories()
+            ))
+    }
+
+    // Maps each interval separately, composing the maps in between only once. Mapping towards an
+    // earlier category yields every value that ends up in the given interval.
+    pub fn map_intervals_between(
+        &self,
+        from: &str,
+        to: &str,
+        intervals: &[Interval],
+    ) -> Result<Vec<Vec<Interval>>, Error> {
+        let from_index = self.category_index(from)?;
+        let to_index = self.category_index(to)?;
+
+        if from_index <= to_index {
+            let composed = self.compose(from, to)?;
+            Ok(intervals
+                .iter()
+                .map(|&interval| composed.map_intervals(&[interval]))
+                .collect())
+        } else {
+            let composed = self.compose(to, from)?;
+            Ok(intervals
+                .iter()
+                .map(|&interval| composed.preimage_intervals(&[interval]))
+                .collect())
+        }
+    }
+
+    pub fn compose(&self, from: &str, to: &str) -> Result<PiecewiseMap, Error> {
+        let from_index = self.category_index(from)?;
+        let to_index = self.category_index(to)?;
+
+        if from_index <= to_index {
+            self.maps[from_index..to_index]
+                .iter()
+                .try_fold(PiecewiseMap::identity(), |composed, map| {
+                    Ok(composed.then(&map.to_piecewise()?))
+                })
+        } else {
+            self.compose(to, from)?.inverse().ok_or(anyhow!(
+                "the maps from {to:?} to {from:?} are not injective and cannot be inverted"
+            ))
+        }
+    }
+}
+
+impl AlmanacMap {
+    pub fn name(&self) -> String {
+        format!("{}-to-{}", self.source, self.destination)
+    }
+
+    pub fn map_value(&self, value: u64) -> Result<u64, Error> {
+        for range in &self.ranges {
+            if range.source()?.contains(value) {
+                range.destination()?;
+                return Ok(range.destination_start + (value - range.source_start));
+            }
+        }
+
+        Ok(value)
+    }
+
+    // Returns the parts of each range's source that the range actually maps.
+    pub fn effective_parts(&self) -> Result<Vec<(&MapRange, Vec<Interval>)>, Error> {
+        let mut covered_intervals: Vec<Interval> = Vec::new();
+        let mut effective_parts = Vec::new();
+
+        // Earlier ranges take precedence, so each range only maps what isn't covered yet.
+        for range in &self.ranges {
+            let source = range.source()?;
+            range.destination()?;
+            let uncovered_parts = covered_intervals
+                .iter()
+                .fold(vec![source], |parts, covered| {
+                    parts
+                        .iter()
+                        .flat_map(|part| part.difference(covered))
+                        .collect()
+                });
+
+            effective_parts.push((range, uncovered_parts));
+            covered_intervals.push(source);
+        }
+
+        Ok(effective_parts)
+    }
+
+    pub fn to_piecewise(&self) -> Result<PiecewiseMap, Error> {
+        let mut segments = Vec::new();
+        let mut covered_intervals = Vec::new();
+
+        for (range, parts) in self.effective_parts()? {
+            segments.extend(parts.iter().map(|part| Segment {
+                source: *part,
+                destination_start: range.destination_start + (part.start - range.source_start),
+            }));
+            covered_intervals.extend(parts);
+        }
+
+        let mut next_uncovered_start = 0;
+        for covered in merge_intervals(covered_intervals) {
+            if next_uncovered_start < covered.start {
+                segments.push(Segment::identity(Interval::new(
+                    next_uncovered_start,
+                    covered.start,
+                )));
+            }
+            next_uncovered_start = covered.end;
+        }
+        if next_uncovered_start < u64::MAX {
+            segments.push(Segment::identity(Interval::new(
+                next_uncovered_start,
+                u64::MAX,
+            )));
+        }
+
+        Ok(PiecewiseMap::from_segments(segments))
+    }
+}
+
+impl MapRange {
+    pub fn source(&self) -> Result<Interval, Error> {
+        let end = self.source_start.checked_add(self.length).ok_or(anyhow!(
+            "line {}: source range {} + {} overflows u64",
+            self.line,
+            self.source_start,
+            self.length
+        ))?;
+        Ok(Interval::new(self.source_start, end))
+    }
+
+    pub fn destination(&self) -> Result<Interval, Error> {
+        let end = self
+            .destination_start
+            .checked_add(self.length)
+            .ok_or(anyhow!(
+                "line {}: destination range {} + {} overflows u64",
+                self.line,
+                self.destination_start,
+                self.length
+            ))?;
+        Ok(Interval::new(self.destination_start, end))
+    }
+}
+
+impl FromStr for Almanac {
+    type Err = Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut lines = string.lines().enumerate();
+
+        let (_, first_line) = lines.next().ok_or(anyhow!("almanac is empty"))?;
+        let seeds = first_line
+            .strip_prefix("seeds:")
+            .ok_or(anyhow!("line 1: expected \"seeds:\""))?
+            .split_whitespace()
+            .map(|word| {
+                word.parse()
+                    .map_err(|_| anyhow!("line 1: invalid seed number {word:?}"))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut maps: Vec<AlmanacMap> = Vec::new();
+        for (index, line) in lines {
+            let line_number = index + 1;
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = line.strip_suffix(" map:") {
+                let (source, destination) = name.split_once("-to-").ok_or(anyhow!(
+                    "line {line_number}: expected a map header like \"seed-to-soil map:\""
+                ))?;
+                maps.push(AlmanacMap {
+                    source: source.to_string(),
+                    destination: destination.to_string(),
+                    ranges: Vec::new(),
+                });
+                continue;
+            }
+
+            let map = maps.last_mut().ok_or(anyhow!(
+                "line {line_number}: range line outside of a map: {line:?}"
+            ))?;
+            let numbers: Vec<u64> = line
+                .split_whitespace()
+                .map(|word| {
+                    word.parse().map_err(|_| {
+                        anyhow!("line {line_number}: invalid number {word:?} in range line")
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            let [destination_start, source_start, length] = numbers[..] else {
+                return Err(anyhow!(
+                    "line {line_number}: expected three numbers in range line, found {}",
+                    numbers.len()
+                ));
+            };
+            map.ranges.push(MapRange {
+                destination_start,
+                source_start,
+                length,
+                line: line_number,
+            });
+        }
+
+        Ok(Almanac {
+            seeds,
+            maps: order_maps_along_chain(maps)?,
+        })
+    }
+}
+
+fn order_maps_along_chain(maps: Vec<AlmanacMap>) -> Result<Vec<AlmanacMap>, Error> {
+    let n_maps = maps.len();
+    let mut maps_by_source: HashMap<String, AlmanacMap> = HashMap::new();
+    let mut sources_by_destination: HashMap<String, String> = HashMap::new();
+
+    for map in &maps {
+        if let Some(other_source) =
+            sources_by_destination.insert(map.destination.clone(), map.source.clone())
+        {
+            return Err(anyhow!(
+                "category {:?} is the destination of both {other_source:?} and {:?}",
+                map.destination,
+                map.source
+            ));
+        }
+    }
+    if let Some(source) = sources_by_destination.get("seed") {
+        return Err(anyhow!(
+            "category \"seed\" must not be mapped to, but {source:?} is"
+        ));
+    }
+
+    for map in maps {
+        if maps_by_source.contains_key(&map.source) {
+            return Err(anyhow!(
+                "category {:?} is the source of more than one map",
+                map.source
+            ));
+        }
+        maps_by_source.insert(map.source.clone(), map);
+    }
+
+    let mut ordered_maps = Vec::new();
+    let mut category = "seed".to_string();
+    while let Some(map) = maps_by_source.remove(&category) {
+        category = map.destination.clone();
+        ordered_maps.push(map);
+    }
+
+    if ordered_maps.len() != n_maps {
+        let mut unreachable: Vec<&String> = maps_by_source.keys().collect();
+        unreachable.sort();
+        return Err(anyhow!(
+            "the maps from {unreachable:?} are not connected to the chain starting at \"seed\""
+        ));
+    }
+
+    Ok(ordered_maps)
+}
@@ -0,0 +1,181 @@
+use crate::{
+    almanac::{AlmanacMap, MapRange},
+    interval::{merge_intervals, Interval},
+};
+use itertools::Itertools;
+use std::fmt::{self, Display, Formatter};
+
+pub enum Finding {
+    OverlappingSources {
+        first_line: usize,
+        second_line: usize,
+        overlap: Interval,
+    },
+    OverlappingDestinations {
+        first_line: usize,
+        second_line: usize,
+        overlap: Interval,
+    },
+    DestinationOverlapsUnmapped {
+        line: usize,
+        overlap: Interval,
+    },
+    Gap(Interval),
+    Overflow {
+        line: usize,
+        start_description: &'static str,
+        start: u64,
+        length: u64,
+    },
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Finding::OverlappingSources {
+                first_line,
+                second_line,
+                overlap,
+            } => write!(
+                f,
+                "source ranges on lines {first_line} and {second_line} overlap in {overlap}, \
+                 line {first_line} wins"
+            ),
+            Finding::OverlappingDestinations {
+                first_line,
+                second_line,
+                overlap,
+            } => write!(
+                f,
+                "destination ranges on lines {first_line} and {second_line} overlap in \
+                 {overlap}, the map is not injective"
+            ),
+            Finding::DestinationOverlapsUnmapped { line, overlap } => write!(
+                f,
+                "destination range on line {line} overlaps the unmapped values {overlap}, \
+                 the map is not injective"
+            ),
+            Finding::Gap(gap) => {
+                write!(f, "values {gap} are not mapped and pass through unchanged")
+            }
+            Finding::Overflow {
+                line,
+                start_description,
+                start,
+                length,
+            } => write!(
+                f,
+                "{start_description} start {start} plus length {length} on line {line} \
+                 overflows"
+            ),
+        }
+    }
+}
+
+pub fn lint_map(map: &AlmanacMap) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    let mut valid_ranges: Vec<(&MapRange, Interval)> = Vec::new();
+    for range in &map.ranges {
+        let source_end = range.source_start.checked_add(range.length);
+        let destination_end = range.destination_start.checked_add(range.length);
+
+        if source_end.is_none() {
+            findings.push(Finding::Overflow {
+                line: range.line,
+                start_description: "source",
+                start: range.source_start,
+                length: range.length,
+            });
+        }
+        if destination_end.is_none() {
+            findings.push(Finding::Overflow {
+                line: range.line,
+                start_description: "destination",
+                start: range.destination_start,
+                length: range.length,
+            });
+        }
+
+        if let (Some(source_end), Some(_)) = (source_end, destination_end) {
+            valid_ranges.push((range, Interval::new(range.source_start, source_end)));
+        }
+    }
+
+    // Only the ranges without overflow take part in the remaining checks.
+    let valid_map = AlmanacMap {
+        source: map.source.clone(),
+        destination: map.destination.clone(),
+        ranges: valid_ranges.iter().map(|&(range, _)| *range).collect(),
+    };
+    let effective_parts = valid_map
+        .effective_parts()
+        .expect("ranges without overflow have valid sources and destinations");
+    let effective_destinations: Vec<Vec<Interval>> = effective_parts
+        .iter()
+        .map(|(range, parts)| {
+            parts
+                .iter()
+                .map(|part| {
+                    let destination_start =
+                        range.destination_start + (part.start - range.source_start);
+                    Interval::new(destination_start, destination_start + part.len())
+                })
+                .collect()
+        })
+        .collect();
+
+    for (index, (first, first_source)) in valid_ranges.iter().enumerate() {
+        for (offset, (second, second_source)) in valid_ranges[index + 1..].iter().enumerate() {
+            let source_overlap = first_source.intersection(second_source);
+            if !source_overlap.is_empty() {
+                findings.push(Finding::OverlappingSources {
+                    first_line: first.line,
+                    second_line: second.line,
+                    overlap: source_overlap,
+                });
+            }
+
+            let destination_pairs = effective_destinations[index]
+                .iter()
+                .cartesian_product(&effective_destinations[index + 1 + offset]);
+            for (first_destination, second_destination) in destination_pairs {
+                let destination_overlap = first_destination.intersection(second_destination);
+                if !destination_overlap.is_empty() {
+                    findings.push(Finding::OverlappingDestinations {
+                        first_line: first.line,
+                        second_line: second.line,
+                        overlap: destination_overlap,
+                    });
+                }
+            }
+        }
+    }
+
+    let covered_sources = merge_intervals(valid_ranges.iter().map(|&(_, source)| source).collect());
+    let mut unmapped = vec![Interval::new(0, u64::MAX)];
+    for covered in &covered_sources {
+        unmapped = unmapped
+            .iter()
+            .flat_map(|interval| interval.difference(covered))
+            .collect();
+    }
+
+    for ((range, _), destinations) in valid_ranges.iter().zip(&effective_destinations) {
+        for (destination, unmapped_interval) in destinations.iter().cartesian_product(&unmapped) {
+            let overlap = destination.intersection(unmapped_interval);
+            if !overlap.is_empty() {
+                findings.push(Finding::DestinationOverlapsUnmapped {
+                    line: range.line,
+                    overlap,
+                });
+            }
+        }
+    }
+
+    for pair in covered_sources.windows(2) {
+        findings.push(Finding::Gap(Interval::new(pair[0].end, pair[1].start)));
+    }
+
+    findings
+}
@@ -1,66 +1,136 @@
-use eyre::{Error, Result};
+use almanac::Almanac;
+use eyre::{anyhow, Error, Result};
+use interval::Interval;
 use itertools::Itertools;
-use std::fs;
+use rayon::prelude::*;
+use std::{env, fs};
 
-fn main() -> Result<(), Error> {
-    let input = fs::read_to_string("input")?.replace("\r\n", "\n");
-    let (first_line, remaining_input) = input.split_once("\n\n").unwrap();
-
-    let (_, seed_numbers) = first_line.split_once(':').unwrap();
-    let seed_numbers = numbers_in(seed_numbers);
+mod almanac;
+mod interval;
+mod lint;
+mod piecewise;
 
-    let mut maps = Vec::new();
-
-    for paragraph in remaining_input.split_terminator("\n\n") {
-        let mut map = Vec::new();
+fn main() -> Result<(), Error> {
+    let input = fs::read_to_string("input")?;
+    let almanac: Almanac = input.parse()?;
 
-        for line in paragraph.lines() {
-            let numbers = numbers_in(line);
-            if let [x, y, z] = numbers[..] {
-                map.push((x, y, z));
+    let args: Vec<String> = env::args().skip(1).collect();
+    let last_category = *almanac.categories().last().unwrap();
+    match args.first().map(String::as_str) {
+        Some("map") => return map_between_categories(&almanac, &args[1..]),
+        Some("lint") => {
+            for map in almanac.maps() {
+                println!("{} map:", map.name());
+                for finding in lint::lint_map(map) {
+                    println!("  {finding}");
+                }
             }
+            return Ok(());
+        }
+        Some("compose") => {
+            let (from, to) = match &args[1..] {
+                [] => ("seed", last_category),
+                [from, to] => (from.as_str(), to.as_str()),
+                _ => return Err(anyhow!("usage: compose [<from category> <to category>]")),
+            };
+            print!("{}", almanac.compose(from, to)?);
+            return Ok(());
         }
+        _ => (),
+    }
 
-        maps.push(map);
+    let seed_to_location = almanac.compose("seed", last_category)?;
+
+    let task_1_seed_number = almanac
+        .seeds()
+        .par_iter()
+        .min_by_key(|&&seed_number| seed_to_location.map_value(seed_number))
+        .ok_or(anyhow!("almanac contains no seeds"))?;
+    let task_1_path = almanac.path(*task_1_seed_number)?;
+    print_solution(1, &almanac, &task_1_path);
+
+    if !almanac.seeds().len().is_multiple_of(2) {
+        return Err(anyhow!(
+            "task 2 reads the seeds as start and length pairs, but there are {} seed numbers",
+            almanac.seeds().len()
+        ));
     }
+    let task_2_seed_intervals: Vec<Interval> = almanac
+        .seeds()
+        .iter()
+        .tuples()
+        .map(|(&seed_number, &range)| {
+            let end = seed_number
+                .checked_add(range)
+                .ok_or(anyhow!("seed range {seed_number} + {range} overflows u64"))?;
+            Ok(Interval::new(seed_number, end))
+        })
+        .collect::<Result<_, Error>>()?;
+
+    let (_, task_2_seed_number) = task_2_seed_intervals
+        .par_iter()
+        .filter_map(|interval| seed_to_location.min_destination_in(interval))
+        .min()
+        .ok_or(anyhow!("almanac contains no seed ranges"))?;
+    print_solution(2, &almanac, &almanac.path(task_2_seed_number)?);
 
-    let map_to_location_numbers = |seed_numbers: &Vec<usize>| -> Vec<usize> {
-        seed_numbers
+    Ok(())
+}
+
+fn print_solution(task: usize, almanac: &Almanac, path: &[u64]) {
+    println!("Task {task}: {}", path.last().unwrap());
+    println!(
+        "  {}",
+        almanac
+            .categories()
             .iter()
-            .map(|&seed_number| {
-                let mut number_to_map = seed_number;
-                for map in &maps {
-                    for &(dest_start, source_start, range) in map {
-                        if (source_start..(source_start + range)).contains(&number_to_map) {
-                            let offset = source_start.abs_diff(number_to_map);
-                            number_to_map = dest_start + offset;
-                            break;
-                        }
-                    }
-                }
-                number_to_map
-            })
-            .collect()
-    };
+            .zip(path)
+            .map(|(category, value)| format!("{category} {value}"))
+            .join(" -> ")
+    );
+}
 
-    let task_1_location_numbers = map_to_location_numbers(&seed_numbers);
-    println!("Task 1: {}", task_1_location_numbers.iter().min().unwrap());
+fn map_between_categories(almanac: &Almanac, args: &[String]) -> Result<(), Error> {
+    let [from, to, values @ ..] = args else {
+        return Err(anyhow!(
+            "usage: map <from category> <to category> <value or start..end>..."
+        ));
+    };
 
-    let task_2_seed_numbers: Vec<usize> = seed_numbers
+    let intervals = values
         .iter()
-        .tuples()
-        .flat_map(|(&seed_number, &range)| seed_number..(seed_number + range))
-        .collect();
+        .map(|value| parse_interval(value))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mapped_intervals_per_interval = almanac.map_intervals_between(from, to, &intervals)?;
 
-    let task_2_location_numbers = map_to_location_numbers(&task_2_seed_numbers);
-    println!("Task 2: {}", task_2_location_numbers.iter().min().unwrap());
+    for (interval, mapped_intervals) in intervals.iter().zip(mapped_intervals_per_interval) {
+        println!(
+            "{from} {interval} -> {to} {}",
+            mapped_intervals.iter().join(", ")
+        );
+    }
 
     Ok(())
 }
 
-fn numbers_in(string: &str) -> Vec<usize> {
-    string
-        .split_whitespace()
-        .filter_map(|word| word.parse().ok())
-        .collect()
+fn parse_interval(value: &str) -> Result<Interval, Error> {
+    match value.split_once("..") {
+        Some((start, end)) => {
+            let (start, end): (u64, u64) = (start.parse()?, end.parse()?);
+            if start > end {
+                return Err(anyhow!(
+                    "usage: map <from category> <to category> <value or start..end>..., \
+                     start {start} is after end {end}"
+                ));
+            }
+            Ok(Interval::new(start, end))
+        }
+        None => {
+            let value: u64 = value.parse()?;
+            let end = value.checked_add(1).ok_or(anyhow!(
+                "value {value} is too large, intervals end before u64::MAX"
+            ))?;
+            Ok(Interval::new(value, end))
+        }
+    }
 }
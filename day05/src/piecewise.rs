@@ -0,0 +1,180 @@
+use crate::interval::{merge_intervals, Interval};
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Segment {
+    pub source: Interval,
+    pub destination_start: u64,
+}
+
+impl Segment {
+    pub fn identity(source: Interval) -> Segment {
+        Segment {
+            source,
+            destination_start: source.start,
+        }
+    }
+
+    pub fn destination(&self) -> Interval {
+        Interval::new(
+            self.destination_start,
+            self.destination_start + self.source.len(),
+        )
+    }
+
+    pub fn map_value(&self, value: u64) -> u64 {
+        self.destination_start + (value - self.source.start)
+    }
+
+    fn continues(&self, previous: &Segment) -> bool {
+        previous.source.end == self.source.start
+            && previous.destination().end == self.destination_start
+    }
+}
+
+// Sorted, non-overlapping segments that together cover every value.
+#[derive(Clone, Debug)]
+pub struct PiecewiseMap {
+    segments: Vec<Segment>,
+}
+
+impl PiecewiseMap {
+    pub fn identity() -> PiecewiseMap {
+        PiecewiseMap {
+            segments: vec![Segment::identity(Interval::new(0, u64::MAX))],
+        }
+    }
+
+    pub fn from_segments(mut segments: Vec<Segment>) -> PiecewiseMap {
+        segments.retain(|segment| !segment.source.is_empty());
+        segments.sort_by_key(|segment| segment.source);
+
+        let mut normalized_segments: Vec<Segment> = Vec::new();
+        for segment in segments {
+            match normalized_segments.last_mut() {
+                Some(previous) if segment.continues(previous) => {
+                    previous.source.end = segment.source.end;
+                }
+                _ => normalized_segments.push(segment),
+            }
+        }
+
+        PiecewiseMap {
+            segments: normalized_segments,
+        }
+    }
+
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let segments = self
+            .segments
+            .iter()
+            .flat_map(|segment| {
+                let destination = segment.destination();
+                next.segments.iter().filter_map(move |next_segment| {
+                    let overlap = destination.intersection(&next_segment.source);
+                    (!overlap.is_empty()).then(|| {
+                        let source_start =
+                            segment.source.start + (overlap.start - segment.destination_start);
+                        Segment {
+                            source: Interval::new(source_start, source_start + overlap.len()),
+                            destination_start: next_segment.map_value(overlap.start),
+                        }
+                    })
+                })
+            })
+            .collect();
+
+        PiecewiseMap::from_segments(segments)
+    }
+
+    pub fn inverse(&self) -> Option<PiecewiseMap> {
+        let inverse = PiecewiseMap::from_segments(
+            self.segments
+                .iter()
+                .map(|segment| Segment {
+                    source: segment.destination(),
+                    destination_start: segment.source.start,
+                })
+                .collect(),
+        );
+
+        let is_injective = inverse
+            .segments
+            .windows(2)
+            .all(|pair| pair[0].source.end <= pair[1].source.start);
+        is_injective.then_some(inverse)
+    }
+
+    pub fn map_value(&self, value: u64) -> u64 {
+        let index = self
+            .segments
+            .partition_point(|segment| segment.source.end <= value);
+        match self.segments.get(index) {
+            Some(segment) if segment.source.start <= value => segment.map_value(value),
+            _ => value,
+        }
+    }
+
+    // Returns the smallest value the interval is mapped to together with the value it comes from.
+    pub fn min_destination_in(&self, interval: &Interval) -> Option<(u64, u64)> {
+        self.segments
+            .iter()
+            .filter_map(|segment| {
+                let overlap = interval.intersection(&segment.source);
+                (!overlap.is_empty()).then(|| (segment.map_value(overlap.start), overlap.start))
+            })
+            .min()
+    }
+
+    pub fn map_intervals(&self, intervals: &[Interval]) -> Vec<Interval> {
+        let mapped_intervals = intervals
+            .iter()
+            .flat_map(|interval| {
+                self.segments.iter().filter_map(|segment| {
+                    let overlap = interval.intersection(&segment.source);
+                    (!overlap.is_empty()).then(|| {
+                        let start = segment.map_value(overlap.start);
+                        Interval::new(start, start + overlap.len())
+                    })
+                })
+            })
+            .collect();
+
+        merge_intervals(mapped_intervals)
+    }
+
+    pub fn preimage_intervals(&self, intervals: &[Interval]) -> Vec<Interval> {
+        let preimage_intervals = intervals
+            .iter()
+            .flat_map(|interval| {
+                self.segments.iter().filter_map(|segment| {
+                    let overlap = interval.intersection(&segment.destination());
+                    (!overlap.is_empty()).then(|| {
+                        let start =
+                            segment.source.start + (overlap.start - segment.destination_start);
+                        Interval::new(start, start + overlap.len())
+                    })
+                })
+            })
+            .collect();
+
+        merge_intervals(preimage_intervals)
+    }
+}
+
+impl Display for PiecewiseMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for segment in &self.segments {
+            let offset = segment.destination_start as i128 - segment.source.start as i128;
+            writeln!(
+                f,
+                "{}..{} -> {}..{} ({offset:+})",
+                segment.source.start,
+                segment.source.end,
+                segment.destination().start,
+                segment.destination().end
+            )?;
+        }
+        Ok(())
+    }
+}
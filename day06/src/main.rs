@@ -1,64 +1,82 @@
-use eyre::Result;
-use std::fs;
+use eyre::{anyhow, Result};
+use num::BigUint;
+use race::RaceModel;
+use race_sheet::RaceSheet;
+use std::{env, fs};
 
-fn main() -> Result<()> {
-    let input = fs::read_to_string("input")?;
-
-    let compute_n_ways_to_win_per_race = |times_and_distances: &Vec<(usize, usize)>| -> Vec<usize> {
-        times_and_distances
-            .iter()
-            .map(|&(time, distance)| {
-                let mut n_ways_to_win = 0;
+mod race;
+mod race_sheet;
+mod report;
 
-                for time_held_down_button in 1..time {
-                    let distance_traveled = (time - time_held_down_button) * time_held_down_button;
-                    if distance_traveled > distance {
-                        n_ways_to_win += 1;
-                    }
-                }
+fn main() -> Result<()> {
+    let race_sheet: RaceSheet = fs::read_to_string("input")?.parse()?;
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let print_report = args.first().is_some_and(|command| command == "report");
+    if print_report {
+        args.remove(0);
+    }
+    let model = parse_race_model(args.into_iter())?;
 
-                n_ways_to_win
-            })
-            .collect()
-    };
+    let compute_n_ways_to_win_per_race =
+        |times_and_distances: &Vec<(BigUint, BigUint)>| -> Vec<BigUint> {
+            times_and_distances
+                .iter()
+                .map(|(time, distance)| model.n_ways_to_win(time, distance))
+                .collect()
+        };
 
     // task 1
     {
-        let times_and_distances: Vec<(usize, usize)> = {
-            let mut numbers_per_line = input.lines().map(numbers_in);
-            let times = numbers_per_line.next().unwrap();
-            let distances = numbers_per_line.next().unwrap();
-            times.into_iter().zip(distances).collect()
-        };
+        let times_and_distances = race_sheet.races();
+
+        if print_report {
+            println!("Task 1");
+            print!("{}", report::format_report(&model, &times_and_distances));
+        }
 
         let n_ways_to_win_per_race = compute_n_ways_to_win_per_race(&times_and_distances);
         println!(
             "Task 1: {}",
-            n_ways_to_win_per_race.iter().product::<usize>()
+            n_ways_to_win_per_race.iter().product::<BigUint>()
         );
     }
 
     // task 2
     {
-        let merged_numbers: Vec<_> = input
-            .lines()
-            .map(|line| {
-                let (_, numbers) = line.split_once(':').unwrap();
-                numbers.replace(' ', "").parse().unwrap()
-            })
-            .collect();
-        let [time, distance]: [usize; 2] = merged_numbers.try_into().unwrap();
+        let times_and_distances = vec![race_sheet.kerned_race()];
+        if print_report {
+            println!();
+            println!("Task 2");
+            print!("{}", report::format_report(&model, &times_and_distances));
+        }
 
-        let n_ways_to_win_task_2 = compute_n_ways_to_win_per_race(&vec![(time, distance)]);
-        println!("Task 2: {}", n_ways_to_win_task_2.iter().product::<usize>());
+        let n_ways_to_win_task_2 = compute_n_ways_to_win_per_race(&times_and_distances);
+        println!(
+            "Task 2: {}",
+            n_ways_to_win_task_2.iter().product::<BigUint>()
+        );
     }
 
     Ok(())
 }
 
-fn numbers_in(string: &str) -> Vec<usize> {
-    string
-        .split_whitespace()
-        .filter_map(|word| word.parse().ok())
-        .collect()
+fn parse_race_model(mut args: impl Iterator<Item = String>) -> Result<RaceModel> {
+    let mut model = RaceModel::default();
+
+    while let Some(arg) = args.next() {
+        let value: BigUint = args
+            .next()
+            .ok_or(anyhow!("{arg} expects a value"))?
+            .parse()?;
+
+        match arg.as_str() {
+            "--charge-rate" => model.charge_rate = value,
+            "--max-charge-time" => model.max_charge_time = Some(value),
+            "--max-speed" => model.max_speed = Some(value),
+            "--cooldown" => model.cooldown = value,
+            _ => return Err(anyhow!("unknown argument: {arg}")),
+        }
+    }
+
+    Ok(model)
 }
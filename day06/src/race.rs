@@ -0,0 +1,316 @@
+use num::{BigUint, One, Zero};
+
+fn count_hold_times(hold_times: Option<(BigUint, BigUint)>) -> BigUint {
+    match hold_times {
+        Some((min_time_held_down_button, max_time_held_down_button)) => {
+            max_time_held_down_button - min_time_held_down_button + 1u32
+        }
+        None => BigUint::zero(),
+    }
+}
+
+pub fn winning_hold_times(time: &BigUint, record_distance: &BigUint) -> Option<(BigUint, BigUint)> {
+    let distance_traveled =
+        |time_held_down_button: &BigUint| (time - time_held_down_button) * time_held_down_button;
+
+    // The winning hold times lie strictly between the roots of h * (time - h) = record_distance,
+    // i.e. (time ± sqrt(time² - 4 * record_distance)) / 2.
+    let time_squared = time * time;
+    let four_times_record_distance = record_distance * 4u32;
+    if time_squared < four_times_record_distance {
+        return None;
+    }
+    let discriminant = time_squared - four_times_record_distance;
+
+    let half_time = time / 2u32;
+    let mut min_time_held_down_button = (time - discriminant.sqrt()) / 2u32;
+    while min_time_held_down_button <= half_time
+        && distance_traveled(&min_time_held_down_button) <= *record_distance
+    {
+        min_time_held_down_button += 1u32;
+    }
+    if min_time_held_down_button > half_time {
+        return None;
+    }
+    while !min_time_held_down_button.is_zero()
+        && distance_traveled(&(&min_time_held_down_button - 1u32)) > *record_distance
+    {
+        min_time_held_down_button -= 1u32;
+    }
+
+    let max_time_held_down_button = time - &min_time_held_down_button;
+
+    Some((min_time_held_down_button, max_time_held_down_button))
+}
+
+#[derive(Clone, Debug)]
+pub struct RaceModel {
+    pub charge_rate: BigUint,
+    pub max_charge_time: Option<BigUint>,
+    pub max_speed: Option<BigUint>,
+    pub cooldown: BigUint,
+}
+
+impl Default for RaceModel {
+    fn default() -> Self {
+        RaceModel {
+            charge_rate: BigUint::one(),
+            max_charge_time: None,
+            max_speed: None,
+            cooldown: BigUint::zero(),
+        }
+    }
+}
+
+impl RaceModel {
+    pub fn speed(&self, time_held_down_button: &BigUint) -> BigUint {
+        let charge_time = match &self.max_charge_time {
+            Some(max_charge_time) => time_held_down_button.min(max_charge_time),
+            None => time_held_down_button,
+        };
+        let speed = &self.charge_rate * charge_time;
+
+        match &self.max_speed {
+            Some(max_speed) => speed.min(max_speed.clone()),
+            None => speed,
+        }
+    }
+
+    pub fn distance_traveled(&self, time: &BigUint, time_held_down_button: &BigUint) -> BigUint {
+        let time_held_down_button_and_cooldown = time_held_down_button + &self.cooldown;
+        if time_held_down_button_and_cooldown >= *time {
+            return BigUint::zero();
+        }
+
+        self.speed(time_held_down_button) * (time - time_held_down_button_and_cooldown)
+    }
+
+    pub fn is_exactly_solvable(&self) -> bool {
+        self.max_charge_time.is_none() && self.max_speed.is_none()
+    }
+
+    pub fn n_ways_to_win(&self, time: &BigUint, record_distance: &BigUint) -> BigUint {
+        count_hold_times(self.winning_hold_times(time, record_distance))
+    }
+
+    pub fn winning_hold_times(
+        &self,
+        time: &BigUint,
+        record_distance: &BigUint,
+    ) -> Option<(BigUint, BigUint)> {
+        if *time <= self.cooldown || self.charge_rate.is_zero() {
+            return None;
+        }
+
+        if self.is_exactly_solvable() {
+            // charge_rate * h * (time - cooldown - h) > record_distance holds exactly when
+            // h * (time - cooldown - h) > record_distance / charge_rate, rounded down.
+            winning_hold_times(
+                &(time - &self.cooldown),
+                &(record_distance / &self.charge_rate),
+            )
+        } else {
+            self.winning_hold_times_by_search(time, record_distance)
+        }
+    }
+
+    pub fn optimal_hold_time(&self, time: &BigUint) -> BigUint {
+        partition_point(&BigUint::zero(), time, |time_held_down_button| {
+            self.distance_traveled(time, &(time_held_down_button + 1u32))
+                > self.distance_traveled(time, time_held_down_button)
+        })
+    }
+
+    // The distance rises until the boat is fully charged or half the time is used up and falls
+    // afterwards, so the winning hold times form a single range around the peak.
+    pub fn winning_hold_times_by_search(
+        &self,
+        time: &BigUint,
+        record_distance: &BigUint,
+    ) -> Option<(BigUint, BigUint)> {
+        let distance_traveled =
+            |time_held_down_button: &BigUint| self.distance_traveled(time, time_held_down_button);
+
+        let peak = self.optimal_hold_time(time);
+        if distance_traveled(&peak) <= *record_distance {
+            return None;
+        }
+
+        let min_time_held_down_button =
+            partition_point(&BigUint::zero(), &peak, |time_held_down_button| {
+                distance_traveled(time_held_down_button) <= *record_distance
+            });
+        let max_time_held_down_button = partition_point(&peak, time, |time_held_down_button| {
+            distance_traveled(time_held_down_button) > *record_distance
+        }) - 1u32;
+
+        Some((min_time_held_down_button, max_time_held_down_button))
+    }
+}
+
+// Returns the first value in start..end for which the predicate is false, assuming that it is true
+// for all values before that one and false for all values after.
+fn partition_point(
+    start: &BigUint,
+    end: &BigUint,
+    predicate: impl Fn(&BigUint) -> bool,
+) -> BigUint {
+    let (mut low, mut high) = (start.clone(), end.clone());
+
+    while low < high {
+        let middle: BigUint = (&low + &high) / 2u32;
+        if predicate(&middle) {
+            low = middle + 1u32;
+        } else {
+            high = middle;
+        }
+    }
+
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{count_hold_times, winning_hold_times, RaceModel};
+    use num::BigUint;
+
+    fn n_ways_to_win_exactly(time: &BigUint, record_distance: &BigUint) -> BigUint {
+        count_hold_times(winning_hold_times(time, record_distance))
+    }
+
+    fn n_ways_to_win(time: u64, record_distance: u64) -> u64 {
+        n_ways_to_win_exactly(&time.into(), &record_distance.into())
+            .try_into()
+            .unwrap()
+    }
+
+    fn n_ways_to_win_brute_force(time: u64, record_distance: u64) -> u64 {
+        (1..time)
+            .filter(|&held| (time - held) * held > record_distance)
+            .count() as u64
+    }
+
+    #[test]
+    fn sample_races() {
+        assert_eq!(n_ways_to_win(7, 9), 4);
+        assert_eq!(n_ways_to_win(15, 40), 8);
+        assert_eq!(n_ways_to_win(30, 200), 9);
+        assert_eq!(n_ways_to_win(71530, 940200), 71503);
+    }
+
+    #[test]
+    fn distance_equal_to_record_does_not_win() {
+        // Holding for 2 out of 4 milliseconds travels exactly 4 millimeters.
+        assert_eq!(n_ways_to_win(4, 4), 0);
+        // Holding for 1 or 3 milliseconds travels exactly 3 millimeters, only 2 beats it.
+        assert_eq!(n_ways_to_win(4, 3), 1);
+        // Holding for 10 or 20 milliseconds travels exactly 200 millimeters.
+        assert_eq!(n_ways_to_win(30, 200), 9);
+        assert_eq!(n_ways_to_win(30, 199), 11);
+    }
+
+    #[test]
+    fn unwinnable_races() {
+        assert_eq!(n_ways_to_win(0, 0), 0);
+        assert_eq!(n_ways_to_win(1, 0), 0);
+        assert_eq!(n_ways_to_win(10, 25), 0);
+        assert_eq!(n_ways_to_win(10, 1000), 0);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for time in 0..60 {
+            for record_distance in 0..(time * time / 4 + 2) {
+                assert_eq!(
+                    n_ways_to_win(time, record_distance),
+                    n_ways_to_win_brute_force(time, record_distance),
+                    "time {time}, record distance {record_distance}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn large_race() {
+        let time = u64::MAX;
+        let record_distance = u64::MAX;
+        assert_eq!(n_ways_to_win(time, record_distance), time - 3);
+    }
+
+    #[test]
+    fn arbitrary_precision_race() {
+        let ten = BigUint::from(10u32);
+        let time = ten.pow(39);
+        assert_eq!(
+            n_ways_to_win_exactly(&time, &BigUint::from(0u32)),
+            &time - 1u32
+        );
+
+        // Holding for 10^38 or 9 * 10^38 milliseconds travels exactly the record distance.
+        let record_distance = ten.pow(76) * 9u32;
+        let expected = ten.pow(38) * 8u32 - 1u32;
+        assert_eq!(n_ways_to_win_exactly(&time, &record_distance), expected);
+    }
+
+    fn brute_force_with_model(model: &RaceModel, time: u64, record_distance: u64) -> u64 {
+        (0..=time)
+            .filter(|&held| {
+                model.distance_traveled(&time.into(), &held.into()) > record_distance.into()
+            })
+            .count() as u64
+    }
+
+    #[test]
+    fn default_model_matches_plain_races() {
+        let model = RaceModel::default();
+        for (time, record_distance) in [(7u64, 9u64), (15, 40), (30, 200), (71530, 940200)] {
+            let (time, record_distance) = (BigUint::from(time), BigUint::from(record_distance));
+            assert_eq!(
+                model.n_ways_to_win(&time, &record_distance),
+                n_ways_to_win_exactly(&time, &record_distance)
+            );
+            assert_eq!(
+                model.winning_hold_times_by_search(&time, &record_distance),
+                super::winning_hold_times(&time, &record_distance)
+            );
+        }
+    }
+
+    #[test]
+    fn models_match_brute_force() {
+        let models = [
+            RaceModel {
+                charge_rate: 3u32.into(),
+                ..RaceModel::default()
+            },
+            RaceModel {
+                cooldown: 4u32.into(),
+                ..RaceModel::default()
+            },
+            RaceModel {
+                max_charge_time: Some(5u32.into()),
+                ..RaceModel::default()
+            },
+            RaceModel {
+                charge_rate: 2u32.into(),
+                max_speed: Some(7u32.into()),
+                cooldown: 1u32.into(),
+                ..RaceModel::default()
+            },
+        ];
+
+        for model in &models {
+            for time in 0..25u64 {
+                for record_distance in 0..(time * time) {
+                    let expected = brute_force_with_model(model, time, record_distance);
+                    let actual = model.n_ways_to_win(&time.into(), &record_distance.into());
+                    assert_eq!(
+                        actual,
+                        expected.into(),
+                        "{model:?}, time {time}, record {record_distance}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use eyre::{anyhow, Error};
+use num::BigUint;
+use std::str::FromStr;
+
+pub struct RaceSheet {
+    times: SheetRow,
+    distances: SheetRow,
+}
+
+struct SheetRow {
+    numbers: Vec<BigUint>,
+    kerned_number: BigUint,
+}
+
+impl RaceSheet {
+    pub fn races(&self) -> Vec<(BigUint, BigUint)> {
+        self.times
+            .numbers
+            .iter()
+            .cloned()
+            .zip(self.distances.numbers.iter().cloned())
+            .collect()
+    }
+
+    pub fn kerned_race(&self) -> (BigUint, BigUint) {
+        (
+            self.times.kerned_number.clone(),
+            self.distances.kerned_number.clone(),
+        )
+    }
+}
+
+impl FromStr for RaceSheet {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = input.trim_end().lines().collect();
+        let [time_line, distance_line] = lines[..] else {
+            return Err(anyhow!(
+                "expected 2 lines (times and distances), found {}",
+                lines.len()
+            ));
+        };
+
+        let times = parse_row(time_line, 1, "Time")?;
+        let distances = parse_row(distance_line, 2, "Distance")?;
+
+        if times.numbers.len() != distances.numbers.len() {
+            return Err(anyhow!(
+                "found {} times but {} distances",
+                times.numbers.len(),
+                distances.numbers.len()
+            ));
+        }
+
+        Ok(RaceSheet { times, distances })
+    }
+}
+
+fn parse_row(line: &str, line_number: usize, label: &str) -> Result<SheetRow, Error> {
+    let line = line.trim_end_matches('\r');
+    let numbers_text = line
+        .strip_prefix(label)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .ok_or(anyhow!(
+            "line {line_number}: expected the label \"{label}:\", found {line:?}"
+        ))?;
+    let first_column = label.chars().count() + 2;
+
+    let mut numbers = Vec::new();
+    let mut kerned_digits = String::new();
+    for (column, word) in words_with_columns(numbers_text) {
+        let column = first_column + column;
+        if !word.chars().all(|char| char.is_ascii_digit()) {
+            return Err(anyhow!(
+                "line {line_number}, column {column}: invalid number {word:?}"
+            ));
+        }
+        numbers.push(word.parse()?);
+        kerned_digits.push_str(word);
+    }
+
+    if numbers.is_empty() {
+        return Err(anyhow!("line {line_number}: no numbers after \"{label}:\""));
+    }
+
+    Ok(SheetRow {
+        numbers,
+        kerned_number: kerned_digits.parse()?,
+    })
+}
+
+// Splits at whitespace like `str::split_whitespace`, also yielding the 0-based column of each word.
+fn words_with_columns(string: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut word_start = None;
+
+    for (column, (index, char)) in string.char_indices().enumerate() {
+        match (word_start, char.is_whitespace()) {
+            (None, false) => word_start = Some((column, index)),
+            (Some((start_column, start_index)), true) => {
+                words.push((start_column, &string[start_index..index]));
+                word_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((start_column, start_index)) = word_start {
+        words.push((start_column, &string[start_index..]));
+    }
+
+    words
+}
@@ -0,0 +1,114 @@
+use crate::race::RaceModel;
+use num::{BigUint, ToPrimitive, Zero};
+use std::fmt::Write;
+
+const PLOT_WIDTH: usize = 60;
+const PLOT_HEIGHT: usize = 12;
+
+pub fn format_report(model: &RaceModel, times_and_distances: &[(BigUint, BigUint)]) -> String {
+    let mut output = String::new();
+
+    for (index, (time, record_distance)) in times_and_distances.iter().enumerate() {
+        writeln!(
+            output,
+            "Race {}: time {time} ms, record {record_distance} mm",
+            index + 1
+        )
+        .unwrap();
+
+        match model.winning_hold_times(time, record_distance) {
+            Some((min_time_held_down_button, max_time_held_down_button)) => writeln!(
+                output,
+                "  winning hold times: {min_time_held_down_button}..={max_time_held_down_button} ms ({} ways)",
+                model.n_ways_to_win(time, record_distance)
+            ),
+            None => writeln!(output, "  no winning hold times"),
+        }
+        .unwrap();
+
+        let optimal_hold_time = model.optimal_hold_time(time);
+        let optimal_distance = model.distance_traveled(time, &optimal_hold_time);
+        let next_hold_time = &optimal_hold_time + 1u32;
+        let optimal_hold_times =
+            if model.distance_traveled(time, &next_hold_time) == optimal_distance {
+                format!("{optimal_hold_time} or {next_hold_time}")
+            } else {
+                optimal_hold_time.to_string()
+            };
+        let margin = if optimal_distance > *record_distance {
+            format!(
+                "beats the record by {} mm",
+                &optimal_distance - record_distance
+            )
+        } else {
+            format!(
+                "falls short of the record by {} mm",
+                record_distance - &optimal_distance
+            )
+        };
+        writeln!(
+            output,
+            "  optimal hold time: {optimal_hold_times} ms, distance {optimal_distance} mm, {margin}"
+        )
+        .unwrap();
+
+        output.push_str(&plot(model, time, record_distance));
+        output.push('\n');
+    }
+
+    output
+}
+
+fn plot(model: &RaceModel, time: &BigUint, record_distance: &BigUint) -> String {
+    let n_columns = match time.to_usize() {
+        Some(time) if time < PLOT_WIDTH => time + 1,
+        _ => PLOT_WIDTH,
+    };
+    let hold_times: Vec<BigUint> = (0..n_columns)
+        .map(|column| match n_columns {
+            1 => BigUint::zero(),
+            _ => time * column / (n_columns - 1),
+        })
+        .collect();
+    let distances: Vec<BigUint> = hold_times
+        .iter()
+        .map(|hold_time| model.distance_traveled(time, hold_time))
+        .collect();
+
+    let optimal_distance = model.distance_traveled(time, &model.optimal_hold_time(time));
+    let max_distance = optimal_distance
+        .max(record_distance.clone())
+        .max(BigUint::from(1u32));
+    let row_of = |distance: &BigUint| {
+        (distance * (PLOT_HEIGHT - 1) / &max_distance)
+            .to_usize()
+            .unwrap()
+    };
+
+    let mut rows = vec![vec![' '; n_columns]; PLOT_HEIGHT];
+    let record_row = row_of(record_distance);
+    rows[record_row].fill('-');
+    for (column, distance) in distances.iter().enumerate() {
+        rows[row_of(distance)][column] = if distance > record_distance { '*' } else { '.' };
+    }
+
+    let mut output = String::new();
+    for (row_index, row) in rows.iter().enumerate().rev() {
+        let row: String = row.iter().collect();
+        let label = match row_index {
+            _ if row_index == record_row => format!(" record {record_distance} mm"),
+            _ if row_index == PLOT_HEIGHT - 1 => format!(" {max_distance} mm"),
+            _ => String::new(),
+        };
+        writeln!(output, "  |{row}{label}").unwrap();
+    }
+    writeln!(output, "  +{}", "-".repeat(n_columns)).unwrap();
+    writeln!(
+        output,
+        "   0 ms{}{time} ms hold time",
+        " ".repeat(n_columns.saturating_sub(5 + time.to_string().len()))
+    )
+    .unwrap();
+
+    output
+}
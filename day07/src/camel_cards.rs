@@ -1,134 +1,222 @@
 use eyre::{anyhow, Error};
 use itertools::Itertools;
-use std::{cmp::Ordering, str::FromStr};
+use std::{cmp::Ordering, fmt::Display, str::FromStr};
 
 #[derive(Eq, PartialEq, Debug)]
 pub struct Hand {
-    cards: [Card; 5],
+    cards: Vec<Card>,
 }
 
-#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
-pub enum Card {
-    A,
-    K,
-    Q,
-    J,
-    T,
-    Nine,
-    Eight,
-    Seven,
-    Six,
-    Five,
-    Four,
-    Three,
-    Two,
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
+pub struct Card {
+    rank: usize,
+    symbol: char,
+    suit: Option<char>,
 }
 
-impl TryFrom<char> for Card {
-    type Error = Error;
-
-    fn try_from(char: char) -> Result<Self, Self::Error> {
-        match char {
-            'A' => Ok(Card::A),
-            'K' => Ok(Card::K),
-            'Q' => Ok(Card::Q),
-            'J' => Ok(Card::J),
-            'T' => Ok(Card::T),
-            '9' => Ok(Card::Nine),
-            '8' => Ok(Card::Eight),
-            '7' => Ok(Card::Seven),
-            '6' => Ok(Card::Six),
-            '5' => Ok(Card::Five),
-            '4' => Ok(Card::Four),
-            '3' => Ok(Card::Three),
-            '2' => Ok(Card::Two),
-            _ => Err(anyhow!("invalid digit")),
-        }
+impl Card {
+    pub fn rank(self) -> usize {
+        self.rank
+    }
+
+    pub fn symbol(self) -> char {
+        self.symbol
+    }
+
+    pub fn suit(self) -> Option<char> {
+        self.suit
     }
 }
 
-impl FromStr for Hand {
-    type Err = Error;
+#[derive(Clone, Debug)]
+pub struct Deck {
+    ranks: Vec<char>,
+    suits: Vec<char>,
+}
 
-    fn from_str(string: &str) -> Result<Self, Self::Err> {
-        if string.chars().count() != 5 {
-            return Err(anyhow!("invalid string length"));
+impl Deck {
+    pub fn standard() -> Deck {
+        Deck {
+            ranks: "AKQJT98765432".chars().collect(),
+            suits: Vec::new(),
+        }
+    }
+
+    pub fn poker() -> Deck {
+        Deck {
+            suits: "shdc".chars().collect(),
+            ..Deck::standard()
+        }
+    }
+
+    pub fn ranks(&self) -> &[char] {
+        &self.ranks
+    }
+
+    pub fn card(&self, symbol: char, suit: Option<char>) -> Result<Card, Error> {
+        let rank = self
+            .ranks
+            .iter()
+            .position(|&rank| rank == symbol)
+            .ok_or(anyhow!("invalid rank {symbol:?}"))?;
+
+        match suit {
+            Some(suit) if !self.suits.contains(&suit) => Err(anyhow!("invalid suit {suit:?}")),
+            None if !self.suits.is_empty() => Err(anyhow!("missing suit after {symbol:?}")),
+            _ => Ok(Card { rank, symbol, suit }),
+        }
+    }
+
+    pub fn parse_hand(&self, string: &str) -> Result<Hand, Error> {
+        let chars: Vec<char> = string.chars().collect();
+        let card_width = if self.suits.is_empty() { 1 } else { 2 };
+        if chars.is_empty() || !chars.len().is_multiple_of(card_width) {
+            return Err(anyhow!("invalid hand length {}", chars.len()));
         }
 
         let mut cards = Vec::new();
-        for char in string.chars() {
-            let card: Card = char.try_into()?;
+        for (index, card) in chars.chunks(card_width).enumerate() {
+            let card = self
+                .card(card[0], card.get(1).copied())
+                .map_err(|error| anyhow!("card {}: {error}", index + 1))?;
+            if let Some(suit) = card.suit.filter(|_| cards.contains(&card)) {
+                return Err(anyhow!(
+                    "card {}: {}{suit} appears twice, but the deck has only one of each card",
+                    index + 1,
+                    card.symbol
+                ));
+            }
             cards.push(card);
         }
 
-        Ok(Hand {
-            cards: cards.try_into().unwrap(),
-        })
+        Ok(Hand { cards })
     }
 }
 
-impl From<&Hand> for HandCategory {
-    fn from(hand: &Hand) -> Self {
-        if hand.cards.iter().all_equal() {
-            return Self::FiveOfAKind;
+impl FromStr for Deck {
+    type Err = Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let (ranks, suits) = string.split_once(':').unwrap_or((string, ""));
+        let ranks: Vec<char> = ranks.chars().collect();
+        let suits: Vec<char> = suits.chars().collect();
+
+        if ranks.is_empty() {
+            return Err(anyhow!("a deck needs at least one rank"));
+        }
+        if !ranks.iter().all_unique() || !suits.iter().all_unique() {
+            return Err(anyhow!("deck ranks and suits must be unique"));
+        }
+        if ranks.iter().chain(&suits).any(|char| char.is_whitespace()) {
+            return Err(anyhow!("deck ranks and suits must not be whitespace"));
         }
 
-        let n_occurences_per_present_card = hand.cards.iter().counts();
+        Ok(Deck { ranks, suits })
+    }
+}
 
-        let len = n_occurences_per_present_card.len();
-        let four_equal = n_occurences_per_present_card
-            .iter()
-            .any(|(_, &n_occurences)| n_occurences == 4);
-        let three_equal = n_occurences_per_present_card
-            .iter()
-            .any(|(_, &n_occurences)| n_occurences == 3);
-
-        match (len, four_equal, three_equal) {
-            (_, true, _) => Self::FourOfAKind,
-            (2, _, true) => Self::FullHouse,
-            (3, _, true) => Self::ThreeOfAKind,
-            (3, _, false) => Self::TwoPair,
-            (4, _, _) => Self::OnePair,
-            _ => Self::HighCard,
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol)?;
+        if let Some(suit) = self.suit {
+            write!(f, "{suit}")?;
         }
+        Ok(())
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
-enum HandCategory {
-    FiveOfAKind,
-    FourOfAKind,
-    FullHouse,
-    ThreeOfAKind,
-    TwoPair,
-    OnePair,
-    HighCard,
+impl FromStr for Hand {
+    type Err = Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Deck::standard().parse_hand(string)
+    }
 }
 
-impl Hand {
-    fn category(&self) -> HandCategory {
-        self.into()
+// The sizes of the groups of equally ranked cards, largest first.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HandCategory {
+    group_sizes: Vec<usize>,
+}
+
+impl HandCategory {
+    pub fn from_cards(cards: &[Card], is_wildcard: impl Fn(Card) -> bool) -> Self {
+        let n_wildcards = cards.iter().filter(|&&card| is_wildcard(card)).count();
+
+        let mut group_sizes: Vec<usize> = cards
+            .iter()
+            .filter(|&&card| !is_wildcard(card))
+            .counts_by(|card| card.rank)
+            .into_values()
+            .sorted()
+            .rev()
+            .collect();
+
+        // Wildcards always do best by joining the largest group.
+        match group_sizes.first_mut() {
+            Some(group_size) => *group_size += n_wildcards,
+            None => group_sizes.push(n_wildcards),
+        }
+
+        HandCategory { group_sizes }
     }
 }
 
-impl PartialOrd for Hand {
+impl Display for HandCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let number_word = |n: usize| match n {
+            1 => "one".to_string(),
+            2 => "two".to_string(),
+            3 => "three".to_string(),
+            4 => "four".to_string(),
+            5 => "five".to_string(),
+            6 => "six".to_string(),
+            7 => "seven".to_string(),
+            8 => "eight".to_string(),
+            9 => "nine".to_string(),
+            10 => "ten".to_string(),
+            _ => n.to_string(),
+        };
+        let multiples: Vec<usize> = self
+            .group_sizes
+            .iter()
+            .copied()
+            .filter(|&group_size| group_size > 1)
+            .collect();
+
+        match multiples[..] {
+            [] => write!(f, "high card"),
+            [3, 2] => write!(f, "full house"),
+            [2, ..] => write!(f, "{} pair", number_word(multiples.len())),
+            [largest] => write!(f, "{} of a kind", number_word(largest)),
+            _ => write!(f, "full house ({})", multiples.iter().join("+")),
+        }
+    }
+}
+
+impl PartialOrd for HandCategory {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl Ord for Hand {
+impl Ord for HandCategory {
     fn cmp(&self, other: &Self) -> Ordering {
-        if self.category() != other.category() {
-            return self.category().cmp(&other.category());
-        }
+        other.group_sizes.cmp(&self.group_sizes)
+    }
+}
 
-        for (self_card, other_card) in self.cards.iter().zip(&other.cards) {
-            if self_card != other_card {
-                return self_card.cmp(other_card);
-            }
-        }
+impl Hand {
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+}
 
-        Ordering::Equal
+impl Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for card in &self.cards {
+            write!(f, "{card}")?;
+        }
+        Ok(())
     }
 }
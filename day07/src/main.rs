@@ -1,25 +1,97 @@
-use camel_cards::Hand;
-use eyre::{Error, Result};
-use std::fs;
+use camel_cards::{Deck, Hand};
+use eyre::{anyhow, Error, Result};
+use itertools::Itertools;
+use rules::{JokerRules, Ruleset, StandardRules};
+use std::{env, fs};
 
 mod camel_cards;
+mod poker;
+mod report;
+mod rules;
 
 fn main() -> Result<(), Error> {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let mut take_option = |option: &str| -> Result<Option<String>, Error> {
+        match args.iter().position(|arg| arg == option) {
+            Some(index) if index + 1 < args.len() => {
+                args.remove(index);
+                Ok(Some(args.remove(index)))
+            }
+            Some(_) => Err(anyhow!("{option} expects a value")),
+            None => Ok(None),
+        }
+    };
+    let deck_name = take_option("--deck")?;
+    let print_report = args.first().is_some_and(|command| command == "report");
+    if print_report {
+        args.remove(0);
+    }
+    let deck = match deck_name {
+        Some(deck) => deck.parse()?,
+        None if args.first().is_some_and(|name| name == "poker") => Deck::poker(),
+        None => Deck::standard(),
+    };
+
     let input = fs::read_to_string("input")?;
 
     let mut hands_with_bids: Vec<(Hand, usize)> = Vec::new();
-    for line in input.lines() {
-        let (hand, bid) = line.split_once(' ').unwrap();
-        hands_with_bids.push((hand.parse()?, bid.parse()?));
+    for (index, line) in input.lines().enumerate() {
+        let parse_hand_with_bid = || -> Result<(Hand, usize), Error> {
+            let (hand, bid) = line
+                .split_once(' ')
+                .ok_or(anyhow!("missing bid after the hand"))?;
+            let hand = deck.parse_hand(hand)?;
+            let bid = bid.parse().map_err(|_| anyhow!("invalid bid: {bid:?}"))?;
+            Ok((hand, bid))
+        };
+        hands_with_bids
+            .push(parse_hand_with_bid().map_err(|error| anyhow!("line {}: {error}", index + 1))?);
     }
-    hands_with_bids.sort_by(|(hand_a, _), (hand_b, _)| hand_b.cmp(hand_a));
 
-    let total_winnings = hands_with_bids
-        .iter()
-        .enumerate()
-        .fold(0, |acc, (index, (_, bid))| acc + (index + 1) * bid);
+    let tasks: Vec<(String, Box<dyn Ruleset>)> = match args.first() {
+        None => vec![
+            ("Task 1".to_string(), Box::new(StandardRules)),
+            ("Task 2".to_string(), Box::new(JokerRules)),
+        ],
+        Some(name) => vec![(name.clone(), rules::ruleset_from_name(name, &deck)?)],
+    };
+
+    for (task, ruleset) in &tasks {
+        if let Some((index, _)) = hands_with_bids
+            .iter()
+            .find_position(|(hand, _)| hand.cards().len() < ruleset.min_hand_size())
+        {
+            return Err(anyhow!(
+                "line {}: {task} needs hands of at least {} cards",
+                index + 1,
+                ruleset.min_hand_size()
+            ));
+        }
+    }
+
+    for (task, ruleset) in tasks {
+        let total_winnings = total_winnings(&mut hands_with_bids, ruleset.as_ref());
 
-    println!("Task 1: {total_winnings}");
+        if print_report {
+            println!("{task}");
+            print!(
+                "{}",
+                report::format_report(&hands_with_bids, ruleset.as_ref())
+            );
+            println!();
+        }
+
+        println!("{task}: {total_winnings}");
+    }
 
     Ok(())
 }
+
+fn total_winnings(hands_with_bids: &mut [(Hand, usize)], ruleset: &dyn Ruleset) -> usize {
+    hands_with_bids.sort_by(|(hand_a, _), (hand_b, _)| ruleset.compare(hand_b, hand_a));
+
+    hands_with_bids
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (index, (_, bid))| acc + (index + 1) * bid)
+}
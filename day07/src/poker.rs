@@ -0,0 +1,151 @@
+use crate::{
+    camel_cards::{Card, Hand},
+    rules::{Decider, Ruleset},
+};
+use itertools::Itertools;
+use std::{cmp::Ordering, fmt::Display};
+
+pub const POKER_HAND_SIZE: usize = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PokerCategory {
+    StraightFlush,
+    FourOfAKind,
+    FullHouse,
+    Flush,
+    Straight,
+    ThreeOfAKind,
+    TwoPair,
+    OnePair,
+    HighCard,
+}
+
+impl Display for PokerCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            PokerCategory::StraightFlush => "straight flush",
+            PokerCategory::FourOfAKind => "four of a kind",
+            PokerCategory::FullHouse => "full house",
+            PokerCategory::Flush => "flush",
+            PokerCategory::Straight => "straight",
+            PokerCategory::ThreeOfAKind => "three of a kind",
+            PokerCategory::TwoPair => "two pair",
+            PokerCategory::OnePair => "one pair",
+            PokerCategory::HighCard => "high card",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PokerValue {
+    category: PokerCategory,
+    kickers: Vec<usize>,
+}
+
+pub fn best_poker_value(hand: &Hand, n_ranks: usize) -> PokerValue {
+    hand.cards()
+        .iter()
+        .copied()
+        .combinations(POKER_HAND_SIZE)
+        .map(|cards| poker_value(&cards, n_ranks))
+        .min()
+        .unwrap()
+}
+
+fn poker_value(cards: &[Card], n_ranks: usize) -> PokerValue {
+    // Larger groups first, then stronger ranks, which is the order kickers are compared in.
+    let groups: Vec<(usize, usize)> = cards
+        .iter()
+        .counts_by(|card| card.rank())
+        .into_iter()
+        .map(|(rank, group_size)| (group_size, rank))
+        .sorted_by(|(size_a, rank_a), (size_b, rank_b)| size_b.cmp(size_a).then(rank_a.cmp(rank_b)))
+        .collect();
+    let group_sizes: Vec<usize> = groups.iter().map(|&(group_size, _)| group_size).collect();
+    let ranks: Vec<usize> = groups.iter().map(|&(_, rank)| rank).collect();
+
+    let is_flush = cards.iter().map(|card| card.suit()).all_equal() && cards[0].suit().is_some();
+    let straight_high_rank = match ranks[..] {
+        [highest, .., lowest] if ranks.len() == POKER_HAND_SIZE && lowest - highest == 4 => {
+            Some(highest)
+        }
+        // The ace plays low in the wheel A-5-4-3-2, which makes the five its highest card.
+        [0, second, .., lowest]
+            if ranks.len() == POKER_HAND_SIZE && lowest == n_ranks - 1 && second == n_ranks - 4 =>
+        {
+            Some(second)
+        }
+        _ => None,
+    };
+
+    let category = match (straight_high_rank, is_flush, &group_sizes[..]) {
+        (Some(_), true, _) => PokerCategory::StraightFlush,
+        (_, _, [4, ..]) => PokerCategory::FourOfAKind,
+        (_, _, [3, 2]) => PokerCategory::FullHouse,
+        (_, true, _) => PokerCategory::Flush,
+        (Some(_), _, _) => PokerCategory::Straight,
+        (_, _, [3, ..]) => PokerCategory::ThreeOfAKind,
+        (_, _, [2, 2, ..]) => PokerCategory::TwoPair,
+        (_, _, [2, ..]) => PokerCategory::OnePair,
+        _ => PokerCategory::HighCard,
+    };
+
+    let kickers = match (category, straight_high_rank) {
+        (PokerCategory::StraightFlush | PokerCategory::Straight, Some(high_rank)) => {
+            vec![high_rank]
+        }
+        _ => ranks,
+    };
+
+    PokerValue { category, kickers }
+}
+
+pub struct PokerRules {
+    pub n_ranks: usize,
+}
+
+impl Ruleset for PokerRules {
+    fn card_rank(&self, card: Card) -> usize {
+        card.rank()
+    }
+
+    fn min_hand_size(&self) -> usize {
+        POKER_HAND_SIZE
+    }
+
+    fn category_name(&self, hand: &Hand) -> String {
+        best_poker_value(hand, self.n_ranks).category.to_string()
+    }
+
+    fn decide(&self, hand_a: &Hand, hand_b: &Hand) -> Decider {
+        let value_a = best_poker_value(hand_a, self.n_ranks);
+        let value_b = best_poker_value(hand_b, self.n_ranks);
+        if value_a.category != value_b.category {
+            return Decider::Category;
+        }
+
+        let card_of_rank = |hand: &Hand, rank: usize| -> Card {
+            *hand
+                .cards()
+                .iter()
+                .find(|card| card.rank() == rank)
+                .unwrap()
+        };
+        value_a
+            .kickers
+            .iter()
+            .zip(&value_b.kickers)
+            .find(|(kicker_a, kicker_b)| kicker_a != kicker_b)
+            .map_or(Decider::Tie, |(&kicker_a, &kicker_b)| {
+                Decider::Cards(
+                    card_of_rank(hand_a, kicker_a),
+                    card_of_rank(hand_b, kicker_b),
+                )
+            })
+    }
+
+    fn compare(&self, hand_a: &Hand, hand_b: &Hand) -> Ordering {
+        best_poker_value(hand_a, self.n_ranks).cmp(&best_poker_value(hand_b, self.n_ranks))
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{
+    camel_cards::Hand,
+    rules::{Decider, Ruleset},
+};
+use std::fmt::Write;
+
+const HISTOGRAM_WIDTH: usize = 40;
+
+// Expects the hands sorted from the weakest, which has rank 1, to the strongest.
+pub fn format_report(ranked_hands_with_bids: &[(Hand, usize)], ruleset: &dyn Ruleset) -> String {
+    let category_names: Vec<String> = ranked_hands_with_bids
+        .iter()
+        .map(|(hand, _)| ruleset.category_name(hand))
+        .collect();
+    let hands: Vec<String> = ranked_hands_with_bids
+        .iter()
+        .map(|(hand, _)| hand.to_string())
+        .collect();
+    let hand_width = hands.iter().map(String::len).max().unwrap_or(0).max(4);
+    let category_width = category_names
+        .iter()
+        .map(String::len)
+        .max()
+        .unwrap_or(0)
+        .max(8);
+
+    let mut output = String::new();
+    writeln!(
+        output,
+        "{:>5}  {:hand_width$}  {:>6}  {:category_width$}  {:18}  {:18}  {:>10}",
+        "rank", "hand", "bid", "category", "vs weaker", "vs stronger", "winnings"
+    )
+    .unwrap();
+
+    let decision = |hand: &Hand, neighbour: Option<&(Hand, usize)>| match neighbour {
+        None => "-".to_string(),
+        Some((neighbour, _)) => match ruleset.decide(hand, neighbour) {
+            Decider::Category => "category".to_string(),
+            Decider::Cards(card, neighbour_card) => format!("{card} vs {neighbour_card}"),
+            Decider::Tie => "tie".to_string(),
+        },
+    };
+
+    let mut total_winnings = 0;
+    for (index, (hand, bid)) in ranked_hands_with_bids.iter().enumerate() {
+        let rank = index + 1;
+        let winnings = rank * bid;
+        total_winnings += winnings;
+
+        let weaker_neighbour = index
+            .checked_sub(1)
+            .map(|index| &ranked_hands_with_bids[index]);
+        writeln!(
+            output,
+            "{rank:>5}  {:hand_width$}  {bid:>6}  {:category_width$}  {:18}  {:18}  {winnings:>10}",
+            hands[index],
+            category_names[index],
+            decision(hand, weaker_neighbour),
+            decision(hand, ranked_hands_with_bids.get(index + 1)),
+        )
+        .unwrap();
+    }
+    writeln!(output, "Total winnings: {total_winnings}").unwrap();
+
+    // Categories appear from the strongest to the weakest.
+    let mut histogram: Vec<(&str, usize)> = Vec::new();
+    for category_name in category_names.iter().rev() {
+        match histogram.iter_mut().find(|(name, _)| name == category_name) {
+            Some((_, count)) => *count += 1,
+            None => histogram.push((category_name, 1)),
+        }
+    }
+
+    output.push('\n');
+    let max_count = histogram.iter().map(|&(_, count)| count).max().unwrap_or(1);
+    for (category_name, count) in histogram {
+        writeln!(
+            output,
+            "{category_name:category_width$}  {count:>6}  {}",
+            "#".repeat((count * HISTOGRAM_WIDTH).div_ceil(max_count))
+        )
+        .unwrap();
+    }
+
+    output
+}
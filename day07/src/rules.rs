@@ -0,0 +1,164 @@
+use crate::camel_cards::{Card, Deck, Hand, HandCategory};
+use crate::poker::PokerRules;
+use eyre::{anyhow, Error};
+use itertools::Itertools;
+use std::cmp::Ordering;
+
+// Stronger cards, categories and hands compare as smaller throughout.
+pub trait Ruleset {
+    fn card_rank(&self, card: Card) -> usize;
+
+    fn is_wildcard(&self, _card: Card) -> bool {
+        false
+    }
+
+    fn min_hand_size(&self) -> usize {
+        1
+    }
+
+    fn category(&self, cards: &[Card]) -> HandCategory {
+        HandCategory::from_cards(cards, |card| self.is_wildcard(card))
+    }
+
+    fn category_name(&self, hand: &Hand) -> String {
+        self.category(hand.cards()).to_string()
+    }
+
+    fn tie_break_cards(&self, cards: &[Card]) -> Vec<Card> {
+        cards.to_vec()
+    }
+
+    fn decide(&self, hand_a: &Hand, hand_b: &Hand) -> Decider {
+        if self.category(hand_a.cards()) != self.category(hand_b.cards()) {
+            return Decider::Category;
+        }
+
+        self.tie_break_cards(hand_a.cards())
+            .into_iter()
+            .zip(self.tie_break_cards(hand_b.cards()))
+            .find(|&(card_a, card_b)| self.card_rank(card_a) != self.card_rank(card_b))
+            .map_or(Decider::Tie, |(card_a, card_b)| {
+                Decider::Cards(card_a, card_b)
+            })
+    }
+
+    fn compare(&self, hand_a: &Hand, hand_b: &Hand) -> Ordering {
+        match self.decide(hand_a, hand_b) {
+            Decider::Category => self
+                .category(hand_a.cards())
+                .cmp(&self.category(hand_b.cards())),
+            Decider::Cards(card_a, card_b) => self.card_rank(card_a).cmp(&self.card_rank(card_b)),
+            Decider::Tie => Ordering::Equal,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decider {
+    Category,
+    Cards(Card, Card),
+    Tie,
+}
+
+pub struct StandardRules;
+
+impl Ruleset for StandardRules {
+    fn card_rank(&self, card: Card) -> usize {
+        card.rank()
+    }
+}
+
+pub struct JokerRules;
+
+impl Ruleset for JokerRules {
+    fn card_rank(&self, card: Card) -> usize {
+        match card.symbol() {
+            'J' => usize::MAX,
+            _ => card.rank(),
+        }
+    }
+
+    fn is_wildcard(&self, card: Card) -> bool {
+        card.symbol() == 'J'
+    }
+}
+
+pub struct HouseRules {
+    pub ranking: Vec<char>,
+    pub wildcards: Vec<char>,
+    // Compare the cards sorted by rank instead of in hand order.
+    pub sorted_tie_break: bool,
+}
+
+impl Ruleset for HouseRules {
+    fn card_rank(&self, card: Card) -> usize {
+        self.ranking
+            .iter()
+            .position(|&symbol| symbol == card.symbol())
+            .unwrap()
+    }
+
+    fn is_wildcard(&self, card: Card) -> bool {
+        self.wildcards.contains(&card.symbol())
+    }
+
+    fn tie_break_cards(&self, cards: &[Card]) -> Vec<Card> {
+        if self.sorted_tie_break {
+            cards
+                .iter()
+                .copied()
+                .sorted_by_key(|&card| self.card_rank(card))
+                .collect()
+        } else {
+            cards.to_vec()
+        }
+    }
+}
+
+fn parse_ranks(string: &str, deck: &Deck) -> Result<Vec<char>, Error> {
+    string
+        .chars()
+        .map(|symbol| {
+            if deck.ranks().contains(&symbol) {
+                Ok(symbol)
+            } else {
+                Err(anyhow!("{symbol:?} is not a rank of the deck"))
+            }
+        })
+        .collect()
+}
+
+pub fn ruleset_from_name(name: &str, deck: &Deck) -> Result<Box<dyn Ruleset>, Error> {
+    let mut parts = name.split(':');
+    match parts.next() {
+        Some("standard") if name == "standard" => Ok(Box::new(StandardRules)),
+        Some("joker") if name == "joker" => Ok(Box::new(JokerRules)),
+        Some("poker") if name == "poker" => Ok(Box::new(PokerRules {
+            n_ranks: deck.ranks().len(),
+        })),
+        Some("house") => {
+            let ranking = parse_ranks(parts.next().unwrap_or_default(), deck)?;
+            if ranking.len() != deck.ranks().len() || !ranking.iter().all_unique() {
+                return Err(anyhow!(
+                    "house ranking must list each of the {} ranks of the deck exactly once",
+                    deck.ranks().len()
+                ));
+            }
+            let wildcards = parse_ranks(parts.next().unwrap_or_default(), deck)?;
+            let sorted_tie_break = match parts.next() {
+                None => false,
+                Some("sorted") => true,
+                Some(tie_break) => return Err(anyhow!("unknown tie-break {tie_break:?}")),
+            };
+
+            Ok(Box::new(HouseRules {
+                ranking,
+                wildcards,
+                sorted_tie_break,
+            }))
+        }
+        _ => Err(anyhow!(
+            "unknown ruleset {name:?}, expected standard, joker, poker or house:<ranking>[:<wildcards>[:sorted]]"
+        )),
+    }
+}